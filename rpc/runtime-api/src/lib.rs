@@ -0,0 +1,40 @@
+//! Runtime API definition for the Stp258 currencies module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+// The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+	/// Balances of any currency, native or not, of the Stp258 currencies
+	/// module.
+	pub trait Stp258CurrenciesApi<AccountId, CurrencyId, Balance> where
+		AccountId: Codec,
+		CurrencyId: Codec,
+		Balance: Codec,
+	{
+		/// The free balance of `who` under `currency_id`.
+		fn free_balance(currency_id: CurrencyId, who: AccountId) -> Balance;
+
+		/// The reserved balance of `who` under `currency_id`.
+		fn reserved_balance(currency_id: CurrencyId, who: AccountId) -> Balance;
+
+		/// The free and reserved balance of `who` under `currency_id`.
+		fn total_balance(currency_id: CurrencyId, who: AccountId) -> Balance;
+
+		/// The locks on the balance of `who` under `currency_id`, as
+		/// `(lock_id, amount)` pairs, e.g. from `Pallet::locks`.
+		fn locks(currency_id: CurrencyId, who: AccountId) -> Vec<([u8; 8], Balance)>;
+
+		/// The total issuance of `currency_id`.
+		fn total_issuance(currency_id: CurrencyId) -> Balance;
+
+		/// The `(currency_id, free, reserved)` balances of `who` in the native
+		/// currency and every registered currency.
+		fn all_balances(who: AccountId) -> Vec<(CurrencyId, Balance, Balance)>;
+	}
+}
@@ -0,0 +1,125 @@
+//! RPC interface for the Stp258 currencies module.
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::{fmt::Display, sync::Arc};
+
+pub use stp258_currencies_rpc_runtime_api::Stp258CurrenciesApi as Stp258CurrenciesRuntimeApi;
+
+mod tests;
+
+/// The balances of an account in one currency.
+///
+/// Balances are rendered as strings, as JSON numbers cannot hold every
+/// `u128` value.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrencyBalance<CurrencyId> {
+	pub currency_id: CurrencyId,
+	pub free: String,
+	pub reserved: String,
+}
+
+#[rpc]
+pub trait Stp258CurrenciesApi<BlockHash, AccountId, CurrencyId, Balance> {
+	/// The free balance of `who` under `currency_id`.
+	#[rpc(name = "stp258_freeBalance")]
+	fn free_balance(&self, currency_id: CurrencyId, who: AccountId, at: Option<BlockHash>) -> Result<String>;
+
+	/// The total issuance of `currency_id`.
+	#[rpc(name = "stp258_totalIssuance")]
+	fn total_issuance(&self, currency_id: CurrencyId, at: Option<BlockHash>) -> Result<String>;
+
+	/// The balances of `who` in the native currency and every registered
+	/// currency.
+	#[rpc(name = "stp258_accountPortfolio")]
+	fn account_portfolio(&self, who: AccountId, at: Option<BlockHash>) -> Result<Vec<CurrencyBalance<CurrencyId>>>;
+}
+
+/// Error code of a failed runtime API call.
+const RUNTIME_ERROR: i64 = 1;
+
+fn runtime_error(message: &str, error: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: message.into(),
+		data: Some(format!("{:?}", error).into()),
+	}
+}
+
+/// A struct that implements the [`Stp258CurrenciesApi`].
+pub struct Stp258Currencies<C, Block> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> Stp258Currencies<C, Block> {
+	/// Create new `Stp258Currencies` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+impl<C, Block, AccountId, CurrencyId, Balance>
+	Stp258CurrenciesApi<<Block as BlockT>::Hash, AccountId, CurrencyId, Balance> for Stp258Currencies<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: Stp258CurrenciesRuntimeApi<Block, AccountId, CurrencyId, Balance>,
+	AccountId: Codec,
+	CurrencyId: Codec,
+	Balance: Codec + Display,
+{
+	fn free_balance(
+		&self,
+		currency_id: CurrencyId,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<String> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.free_balance(&at, currency_id, who)
+			.map(|balance| balance.to_string())
+			.map_err(|e| runtime_error("Unable to query free balance.", e))
+	}
+
+	fn total_issuance(&self, currency_id: CurrencyId, at: Option<<Block as BlockT>::Hash>) -> Result<String> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.total_issuance(&at, currency_id)
+			.map(|balance| balance.to_string())
+			.map_err(|e| runtime_error("Unable to query total issuance.", e))
+	}
+
+	fn account_portfolio(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<CurrencyBalance<CurrencyId>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.all_balances(&at, who)
+			.map(|balances| {
+				balances
+					.into_iter()
+					.map(|(currency_id, free, reserved)| CurrencyBalance {
+						currency_id,
+						free: free.to_string(),
+						reserved: reserved.to_string(),
+					})
+					.collect()
+			})
+			.map_err(|e| runtime_error("Unable to query account portfolio.", e))
+	}
+}
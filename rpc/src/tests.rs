@@ -0,0 +1,171 @@
+//! Unit tests for the Stp258 currencies RPC.
+
+#![cfg(test)]
+
+use super::*;
+use sp_api::ApiRef;
+use sp_blockchain::{BlockStatus, Info};
+use sp_runtime::{
+	testing::{Block as TestBlock, ExtrinsicWrapper, Header, H256},
+	traits::NumberFor,
+};
+
+type Block = TestBlock<ExtrinsicWrapper<u64>>;
+type AccountId = u64;
+type CurrencyId = u32;
+type Balance = u128;
+
+const DNAR: CurrencyId = 1;
+const SETT: CurrencyId = 2;
+const ALICE: AccountId = 1;
+const BOB: AccountId = 2;
+
+/// Above 2^53, the largest integer a JSON number holds exactly.
+const LARGE_BALANCE: Balance = 1_000_000_000_000_000_000_000_001;
+
+#[derive(Clone, Default)]
+struct MockApi;
+
+sp_api::mock_impl_runtime_apis! {
+	impl Stp258CurrenciesRuntimeApi<Block, AccountId, CurrencyId, Balance> for MockApi {
+		fn free_balance(currency_id: CurrencyId, who: AccountId) -> Balance {
+			match (currency_id, who) {
+				(DNAR, ALICE) => 100,
+				(SETT, ALICE) => LARGE_BALANCE,
+				_ => 0,
+			}
+		}
+
+		fn reserved_balance(currency_id: CurrencyId, who: AccountId) -> Balance {
+			match (currency_id, who) {
+				(SETT, ALICE) => 50,
+				_ => 0,
+			}
+		}
+
+		fn total_balance(currency_id: CurrencyId, who: AccountId) -> Balance {
+			match (currency_id, who) {
+				(DNAR, ALICE) => 100,
+				(SETT, ALICE) => LARGE_BALANCE + 50,
+				_ => 0,
+			}
+		}
+
+		fn locks(_currency_id: CurrencyId, _who: AccountId) -> Vec<([u8; 8], Balance)> {
+			Vec::new()
+		}
+
+		fn total_issuance(currency_id: CurrencyId) -> Balance {
+			match currency_id {
+				DNAR => 100,
+				SETT => LARGE_BALANCE + 50,
+				_ => 0,
+			}
+		}
+
+		fn all_balances(who: AccountId) -> Vec<(CurrencyId, Balance, Balance)> {
+			match who {
+				ALICE => vec![(DNAR, 100, 0), (SETT, LARGE_BALANCE, 50)],
+				_ => vec![(DNAR, 0, 0)],
+			}
+		}
+	}
+}
+
+struct MockClient;
+
+impl ProvideRuntimeApi<Block> for MockClient {
+	type Api = MockApi;
+
+	fn runtime_api(&self) -> ApiRef<'_, Self::Api> {
+		MockApi.into()
+	}
+}
+
+impl HeaderBackend<Block> for MockClient {
+	fn header(&self, _id: BlockId<Block>) -> sp_blockchain::Result<Option<Header>> {
+		Ok(None)
+	}
+
+	fn info(&self) -> Info<Block> {
+		Info {
+			best_hash: H256::repeat_byte(1),
+			best_number: 1,
+			genesis_hash: H256::zero(),
+			finalized_hash: H256::zero(),
+			finalized_number: 0,
+			number_leaves: 1,
+		}
+	}
+
+	fn status(&self, _id: BlockId<Block>) -> sp_blockchain::Result<BlockStatus> {
+		Ok(BlockStatus::InChain)
+	}
+
+	fn number(&self, _hash: H256) -> sp_blockchain::Result<Option<NumberFor<Block>>> {
+		Ok(None)
+	}
+
+	fn hash(&self, _number: NumberFor<Block>) -> sp_blockchain::Result<Option<H256>> {
+		Ok(None)
+	}
+}
+
+fn api() -> impl Stp258CurrenciesApi<H256, AccountId, CurrencyId, Balance> {
+	Stp258Currencies::<MockClient, Block>::new(Arc::new(MockClient))
+}
+
+#[test]
+fn free_balance_should_work() {
+	let api = api();
+	assert_eq!(api.free_balance(DNAR, ALICE, None), Ok("100".to_string()));
+	assert_eq!(api.free_balance(DNAR, BOB, Some(H256::zero())), Ok("0".to_string()));
+}
+
+#[test]
+fn balances_are_rendered_as_strings_without_precision_loss() {
+	let api = api();
+	assert_eq!(
+		api.free_balance(SETT, ALICE, None),
+		Ok("1000000000000000000000001".to_string())
+	);
+	assert_eq!(
+		api.total_issuance(SETT, None),
+		Ok("1000000000000000000000051".to_string())
+	);
+}
+
+#[test]
+fn total_issuance_should_work() {
+	let api = api();
+	assert_eq!(api.total_issuance(DNAR, None), Ok("100".to_string()));
+}
+
+#[test]
+fn account_portfolio_should_work() {
+	let api = api();
+	assert_eq!(
+		api.account_portfolio(ALICE, None),
+		Ok(vec![
+			CurrencyBalance {
+				currency_id: DNAR,
+				free: "100".to_string(),
+				reserved: "0".to_string(),
+			},
+			CurrencyBalance {
+				currency_id: SETT,
+				free: "1000000000000000000000001".to_string(),
+				reserved: "50".to_string(),
+			},
+		])
+	);
+}
+
+#[test]
+fn account_portfolio_serializes_balances_as_strings() {
+	let portfolio = api().account_portfolio(BOB, None).unwrap();
+	assert_eq!(
+		serde_json::to_string(&portfolio).unwrap(),
+		r#"[{"currencyId":1,"free":"0","reserved":"0"}]"#
+	);
+}
@@ -0,0 +1,493 @@
+//! Benchmarks for the Stp258 currencies module.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::OnInitialize;
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+const LOCK_ID: LockIdentifier = *b"benchmrk";
+
+fn dollar<T: Config>(currency_id: CurrencyIdOf<T>, amount: u32) -> BalanceOf<T> {
+	<Pallet<T> as Stp258Currency<T::AccountId>>::base_unit(currency_id)
+		.max(One::one())
+		.saturating_mul(amount.into())
+}
+
+/// A registered currency other than the native one, the benchmarked runtime
+/// must register at least one in its genesis.
+fn non_native_currency_id<T: Config>() -> CurrencyIdOf<T> {
+	CurrencyRegistry::<T>::iter()
+		.map(|(currency_id, _)| currency_id)
+		.find(|currency_id| *currency_id != T::GetStp258NativeId::get())
+		.expect("a non-native currency is registered; qed")
+}
+
+fn funded_account<T: Config>(
+	name: &'static str,
+	index: u32,
+	currency_id: CurrencyIdOf<T>,
+	amount: BalanceOf<T>,
+) -> T::AccountId {
+	let who: T::AccountId = account(name, index, SEED);
+	<Pallet<T> as Stp258Currency<T::AccountId>>::deposit(currency_id, &who, amount).unwrap();
+	who
+}
+
+/// Give `who` enough native currency for the deposit of a subscription.
+fn fund_subscription_deposit<T: Config>(who: &T::AccountId) {
+	let native_currency_id = T::GetStp258NativeId::get();
+	let amount = T::SubscriptionDeposit::get().saturating_add(dollar::<T>(native_currency_id, 1_000));
+	<Pallet<T> as Stp258Currency<T::AccountId>>::deposit(native_currency_id, who, amount).unwrap();
+}
+
+/// Releases 100 dollars every block from genesis, 10 times.
+fn vesting_schedule<T: Config>(currency_id: CurrencyIdOf<T>) -> VestingScheduleOf<T> {
+	VestingSchedule {
+		start: Zero::zero(),
+		period: One::one(),
+		period_count: 10,
+		per_period: dollar::<T>(currency_id, 100),
+	}
+}
+
+fn metadata<T: Config>() -> CurrencyMetadata<BalanceOf<T>> {
+	CurrencyMetadata {
+		name: b"Benchmark Dollar".to_vec(),
+		symbol: b"BNCH".to_vec(),
+		decimals: 12,
+		is_stablecoin: true,
+		minimum_balance: None,
+	}
+}
+
+benchmarks! {
+	// `transfer` non-native currency
+	transfer_non_native_currency {
+		let currency_id = non_native_currency_id::<T>();
+		let amount = dollar::<T>(currency_id, 1_000);
+		let from: T::AccountId = whitelisted_caller();
+		<Pallet<T> as Stp258Currency<T::AccountId>>::deposit(currency_id, &from, amount)?;
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+	}: transfer(RawOrigin::Signed(from), to_lookup, currency_id, amount)
+	verify {
+		assert_eq!(<Pallet<T> as Stp258Currency<T::AccountId>>::total_balance(currency_id, &to), amount);
+	}
+
+	// `transfer_native_currency`, killing the sender and creating the recipient
+	transfer_native_currency {
+		let currency_id = T::GetStp258NativeId::get();
+		let amount = dollar::<T>(currency_id, 1_000);
+		let from: T::AccountId = whitelisted_caller();
+		<Pallet<T> as Stp258Currency<T::AccountId>>::deposit(currency_id, &from, amount)?;
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+	}: _(RawOrigin::Signed(from), to_lookup, amount)
+	verify {
+		assert_eq!(<Pallet<T> as Stp258Currency<T::AccountId>>::total_balance(currency_id, &to), amount);
+	}
+
+	// `update_balance` non-native currency
+	update_balance_non_native_currency {
+		let currency_id = non_native_currency_id::<T>();
+		let balance = dollar::<T>(currency_id, 1_000);
+		let amount = AmountOf::<T>::try_from(balance).map_err(|_| "balance overflows amount")?;
+		let who: T::AccountId = account("who", 0, SEED);
+		let who_lookup = T::Lookup::unlookup(who.clone());
+		let origin = T::UpdateOrigin::successful_origin();
+	}: {
+		Pallet::<T>::update_balance(origin, who_lookup, currency_id, amount)?;
+	}
+	verify {
+		assert_eq!(<Pallet<T> as Stp258Currency<T::AccountId>>::total_balance(currency_id, &who), balance);
+	}
+
+	// `update_balance` non-native currency, killing the account
+	update_balance_non_native_currency_killing {
+		let currency_id = non_native_currency_id::<T>();
+		let balance = dollar::<T>(currency_id, 1_000);
+		let amount = AmountOf::<T>::try_from(balance).map_err(|_| "balance overflows amount")?;
+		let who = funded_account::<T>("who", 0, currency_id, balance);
+		let who_lookup = T::Lookup::unlookup(who.clone());
+		let origin = T::UpdateOrigin::successful_origin();
+	}: {
+		Pallet::<T>::update_balance(origin, who_lookup, currency_id, -amount)?;
+	}
+	verify {
+		assert_eq!(<Pallet<T> as Stp258Currency<T::AccountId>>::total_balance(currency_id, &who), Zero::zero());
+	}
+
+	// `update_balance` native currency, creating the account
+	update_balance_native_currency_creating {
+		let currency_id = T::GetStp258NativeId::get();
+		let balance = dollar::<T>(currency_id, 1_000);
+		let amount = AmountOf::<T>::try_from(balance).map_err(|_| "balance overflows amount")?;
+		let who: T::AccountId = account("who", 0, SEED);
+		let who_lookup = T::Lookup::unlookup(who.clone());
+		let origin = T::UpdateOrigin::successful_origin();
+	}: {
+		Pallet::<T>::update_balance(origin, who_lookup, currency_id, amount)?;
+	}
+	verify {
+		assert_eq!(<Pallet<T> as Stp258Currency<T::AccountId>>::total_balance(currency_id, &who), balance);
+	}
+
+	// `update_balance` native currency, killing the account
+	update_balance_native_currency_killing {
+		let currency_id = T::GetStp258NativeId::get();
+		let balance = dollar::<T>(currency_id, 1_000);
+		let amount = AmountOf::<T>::try_from(balance).map_err(|_| "balance overflows amount")?;
+		let who = funded_account::<T>("who", 0, currency_id, balance);
+		let who_lookup = T::Lookup::unlookup(who.clone());
+		let origin = T::UpdateOrigin::successful_origin();
+	}: {
+		Pallet::<T>::update_balance(origin, who_lookup, currency_id, -amount)?;
+	}
+	verify {
+		assert_eq!(<Pallet<T> as Stp258Currency<T::AccountId>>::free_balance(currency_id, &who), Zero::zero());
+	}
+
+	register_currency {
+		let currency_id = non_native_currency_id::<T>();
+		CurrencyRegistry::<T>::remove(currency_id);
+	}: _(RawOrigin::Root, currency_id, metadata::<T>())
+	verify {
+		assert_eq!(Pallet::<T>::currency_metadata(currency_id), Some(metadata::<T>()));
+	}
+
+	update_currency_metadata {
+		let currency_id = non_native_currency_id::<T>();
+	}: _(RawOrigin::Root, currency_id, metadata::<T>())
+	verify {
+		assert_eq!(Pallet::<T>::currency_metadata(currency_id), Some(metadata::<T>()));
+	}
+
+	transfer_all {
+		let currency_id = non_native_currency_id::<T>();
+		let amount = dollar::<T>(currency_id, 1_000);
+		let from: T::AccountId = whitelisted_caller();
+		<Pallet<T> as Stp258Currency<T::AccountId>>::deposit(currency_id, &from, amount)?;
+		let locked = amount / 2u32.into();
+		<Pallet<T> as Stp258CurrencyLockable<T::AccountId>>::set_lock(LOCK_ID, currency_id, &from, locked)?;
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+	}: _(RawOrigin::Signed(from), to_lookup, currency_id, false)
+	verify {
+		assert_eq!(<Pallet<T> as Stp258Currency<T::AccountId>>::total_balance(currency_id, &to), amount - locked);
+	}
+
+	transfer_batch {
+		let n in 1 .. T::MaxBatchTransfers::get();
+		let currency_id = non_native_currency_id::<T>();
+		let amount = dollar::<T>(currency_id, 1_000);
+		let from: T::AccountId = whitelisted_caller();
+		<Pallet<T> as Stp258Currency<T::AccountId>>::deposit(currency_id, &from, amount.saturating_mul(n.into()))?;
+		let transfers = (0..n)
+			.map(|i| (T::Lookup::unlookup(account("to", i, SEED)), currency_id, amount))
+			.collect::<Vec<_>>();
+	}: _(RawOrigin::Signed(from.clone()), transfers)
+	verify {
+		assert_eq!(<Pallet<T> as Stp258Currency<T::AccountId>>::free_balance(currency_id, &from), Zero::zero());
+	}
+
+	approve {
+		let currency_id = non_native_currency_id::<T>();
+		let amount = dollar::<T>(currency_id, 1_000);
+		let owner: T::AccountId = whitelisted_caller();
+		let spender: T::AccountId = account("spender", 0, SEED);
+		let spender_lookup = T::Lookup::unlookup(spender.clone());
+	}: _(RawOrigin::Signed(owner.clone()), spender_lookup, currency_id, amount)
+	verify {
+		assert_eq!(Pallet::<T>::allowance(currency_id, &owner, &spender), amount);
+	}
+
+	increase_allowance {
+		let currency_id = non_native_currency_id::<T>();
+		let amount = dollar::<T>(currency_id, 1_000);
+		let owner: T::AccountId = whitelisted_caller();
+		let spender: T::AccountId = account("spender", 0, SEED);
+		let spender_lookup = T::Lookup::unlookup(spender.clone());
+		Allowances::<T>::insert(&owner, (spender.clone(), currency_id), amount);
+	}: _(RawOrigin::Signed(owner.clone()), spender_lookup, currency_id, amount)
+	verify {
+		assert_eq!(Pallet::<T>::allowance(currency_id, &owner, &spender), amount.saturating_add(amount));
+	}
+
+	decrease_allowance {
+		let currency_id = non_native_currency_id::<T>();
+		let amount = dollar::<T>(currency_id, 1_000);
+		let owner: T::AccountId = whitelisted_caller();
+		let spender: T::AccountId = account("spender", 0, SEED);
+		let spender_lookup = T::Lookup::unlookup(spender.clone());
+		Allowances::<T>::insert(&owner, (spender.clone(), currency_id), amount);
+	}: _(RawOrigin::Signed(owner.clone()), spender_lookup, currency_id, amount)
+	verify {
+		assert_eq!(Pallet::<T>::allowance(currency_id, &owner, &spender), Zero::zero());
+	}
+
+	transfer_from {
+		let currency_id = non_native_currency_id::<T>();
+		let amount = dollar::<T>(currency_id, 1_000);
+		let owner = funded_account::<T>("owner", 0, currency_id, amount);
+		let owner_lookup = T::Lookup::unlookup(owner.clone());
+		let spender: T::AccountId = whitelisted_caller();
+		Allowances::<T>::insert(&owner, (spender.clone(), currency_id), amount);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+	}: _(RawOrigin::Signed(spender), owner_lookup, to_lookup, currency_id, amount)
+	verify {
+		assert_eq!(<Pallet<T> as Stp258Currency<T::AccountId>>::total_balance(currency_id, &to), amount);
+	}
+
+	force_set_lock {
+		let currency_id = non_native_currency_id::<T>();
+		let amount = dollar::<T>(currency_id, 1_000);
+		let who = funded_account::<T>("who", 0, currency_id, amount);
+		let who_lookup = T::Lookup::unlookup(who.clone());
+	}: _(RawOrigin::Root, who_lookup, currency_id, LOCK_ID, amount)
+	verify {
+		assert!(<Pallet<T> as Stp258Currency<T::AccountId>>::ensure_can_withdraw(currency_id, &who, amount).is_err());
+	}
+
+	force_remove_lock {
+		let currency_id = non_native_currency_id::<T>();
+		let amount = dollar::<T>(currency_id, 1_000);
+		let who = funded_account::<T>("who", 0, currency_id, amount);
+		let who_lookup = T::Lookup::unlookup(who.clone());
+		<Pallet<T> as Stp258CurrencyLockable<T::AccountId>>::set_lock(LOCK_ID, currency_id, &who, amount)?;
+	}: _(RawOrigin::Root, who_lookup, currency_id, LOCK_ID)
+	verify {
+		assert!(<Pallet<T> as Stp258Currency<T::AccountId>>::ensure_can_withdraw(currency_id, &who, amount).is_ok());
+	}
+
+	pause_currency {
+		let currency_id = non_native_currency_id::<T>();
+		let origin = T::PauseOrigin::successful_origin();
+	}: _<T::Origin>(origin, currency_id)
+	verify {
+		assert!(Pallet::<T>::is_paused(currency_id));
+	}
+
+	unpause_currency {
+		let currency_id = non_native_currency_id::<T>();
+		PausedCurrencies::<T>::insert(currency_id, ());
+		let origin = T::PauseOrigin::successful_origin();
+	}: _<T::Origin>(origin, currency_id)
+	verify {
+		assert!(!Pallet::<T>::is_paused(currency_id));
+	}
+
+	freeze_account {
+		let currency_id = non_native_currency_id::<T>();
+		let who: T::AccountId = account("who", 0, SEED);
+		let who_lookup = T::Lookup::unlookup(who.clone());
+		let origin = T::FreezeOrigin::successful_origin();
+	}: _<T::Origin>(origin, who_lookup, currency_id)
+	verify {
+		assert!(Pallet::<T>::is_frozen(currency_id, &who));
+	}
+
+	thaw_account {
+		let currency_id = non_native_currency_id::<T>();
+		let who: T::AccountId = account("who", 0, SEED);
+		let who_lookup = T::Lookup::unlookup(who.clone());
+		FrozenAccounts::<T>::insert(&who, currency_id, ());
+		let origin = T::FreezeOrigin::successful_origin();
+	}: _<T::Origin>(origin, who_lookup, currency_id)
+	verify {
+		assert!(!Pallet::<T>::is_frozen(currency_id, &who));
+	}
+
+	set_fee_currency {
+		let currency_id = non_native_currency_id::<T>();
+		let who: T::AccountId = whitelisted_caller();
+	}: _(RawOrigin::Signed(who.clone()), Some(currency_id))
+	verify {
+		assert_eq!(FeeCurrency::<T>::get(&who), Some(currency_id));
+	}
+
+	set_transfer_fee {
+		let currency_id = non_native_currency_id::<T>();
+		let fee = TransferFee {
+			flat: dollar::<T>(currency_id, 1),
+			rate: Perbill::from_percent(1),
+			min: dollar::<T>(currency_id, 1),
+			max: dollar::<T>(currency_id, 100),
+		};
+		let origin = T::UpdateOrigin::successful_origin();
+	}: _<T::Origin>(origin, currency_id, Some(fee.clone()))
+	verify {
+		assert_eq!(TransferFees::<T>::get(currency_id), Some(fee));
+	}
+
+	// fee legs of a transfer, on top of the transfer itself
+	charge_transfer_fee {
+		let currency_id = non_native_currency_id::<T>();
+		let fee = dollar::<T>(currency_id, 10);
+		TransferFees::<T>::insert(currency_id, TransferFee {
+			flat: fee,
+			rate: Perbill::from_percent(0),
+			min: fee,
+			max: fee,
+		});
+		let from = funded_account::<T>("from", 0, currency_id, dollar::<T>(currency_id, 1_000));
+		let amount = dollar::<T>(currency_id, 100);
+	}: {
+		Pallet::<T>::charge_transfer_fee(currency_id, &from, amount, ExistenceRequirement::AllowDeath)?;
+	}
+	verify {
+		assert_eq!(
+			<Pallet<T> as Stp258Currency<T::AccountId>>::free_balance(currency_id, &from),
+			dollar::<T>(currency_id, 990)
+		);
+	}
+
+	vested_transfer {
+		let currency_id = non_native_currency_id::<T>();
+		let schedule = vesting_schedule::<T>(currency_id);
+		let from = funded_account::<T>("from", 0, currency_id, dollar::<T>(currency_id, 2_000));
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+	}: _(RawOrigin::Signed(from), to_lookup, currency_id, schedule)
+	verify {
+		assert_eq!(VestingSchedules::<T>::get(currency_id, &to).len(), 1);
+	}
+
+	claim {
+		let currency_id = non_native_currency_id::<T>();
+		let who = funded_account::<T>("who", 0, currency_id, dollar::<T>(currency_id, 2_000));
+		VestingSchedules::<T>::insert(currency_id, &who, vec![vesting_schedule::<T>(currency_id)]);
+		Pallet::<T>::update_vesting_lock(currency_id, &who)?;
+		frame_system::Module::<T>::set_block_number(5u32.into());
+	}: _(RawOrigin::Signed(who.clone()), currency_id)
+	verify {
+		assert_eq!(VestingSchedules::<T>::get(currency_id, &who).len(), 1);
+	}
+
+	update_vesting_schedules {
+		let n in 1 .. T::MaxVestingSchedules::get();
+		let currency_id = non_native_currency_id::<T>();
+		let who = funded_account::<T>("who", 0, currency_id, dollar::<T>(currency_id, 1_000).saturating_mul(n.into()));
+		let who_lookup = T::Lookup::unlookup(who.clone());
+		let schedules = vec![vesting_schedule::<T>(currency_id); n as usize];
+		let origin = T::UpdateOrigin::successful_origin();
+	}: _<T::Origin>(origin, who_lookup, currency_id, schedules)
+	verify {
+		assert_eq!(VestingSchedules::<T>::get(currency_id, &who).len(), n as usize);
+	}
+
+	schedule_transfer {
+		let currency_id = non_native_currency_id::<T>();
+		let amount = dollar::<T>(currency_id, 1_000);
+		let from = funded_account::<T>("from", 0, currency_id, amount.saturating_mul(2u32.into()));
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to);
+		let at = frame_system::Module::<T>::block_number() + One::one();
+	}: _(RawOrigin::Signed(from), to_lookup, currency_id, amount, at)
+	verify {
+		assert_eq!(TransferAgenda::<T>::get(at).len(), 1);
+	}
+
+	cancel_scheduled_transfer {
+		let currency_id = non_native_currency_id::<T>();
+		let amount = dollar::<T>(currency_id, 1_000);
+		let from = funded_account::<T>("from", 0, currency_id, amount.saturating_mul(2u32.into()));
+		let to: T::AccountId = account("to", 0, SEED);
+		let at = frame_system::Module::<T>::block_number() + One::one();
+		Pallet::<T>::schedule_transfer(
+			RawOrigin::Signed(from.clone()).into(),
+			T::Lookup::unlookup(to),
+			currency_id,
+			amount,
+			at,
+		)?;
+		let id = NextScheduledTransferId::<T>::get() - 1;
+	}: _(RawOrigin::Signed(from), id)
+	verify {
+		assert!(ScheduledTransfers::<T>::get(id).is_none());
+	}
+
+	execute_scheduled_transfers {
+		let n in 1 .. T::MaxScheduledPerBlock::get();
+		let currency_id = non_native_currency_id::<T>();
+		let amount = dollar::<T>(currency_id, 1_000);
+		let to: T::AccountId = account("to", 0, SEED);
+		let at = frame_system::Module::<T>::block_number() + One::one();
+		for i in 0 .. n {
+			let from = funded_account::<T>("from", i, currency_id, amount.saturating_mul(2u32.into()));
+			Pallet::<T>::schedule_transfer(
+				RawOrigin::Signed(from).into(),
+				T::Lookup::unlookup(to.clone()),
+				currency_id,
+				amount,
+				at,
+			)?;
+		}
+	}: {
+		Pallet::<T>::on_initialize(at);
+	}
+	verify {
+		assert_eq!(
+			<Pallet<T> as Stp258Currency<T::AccountId>>::free_balance(currency_id, &to),
+			amount.saturating_mul(n.into())
+		);
+	}
+
+	create_subscription {
+		let currency_id = non_native_currency_id::<T>();
+		let payer: T::AccountId = whitelisted_caller();
+		fund_subscription_deposit::<T>(&payer);
+		let merchant: T::AccountId = account("merchant", 0, SEED);
+		let merchant_lookup = T::Lookup::unlookup(merchant);
+		let amount = dollar::<T>(currency_id, 100);
+	}: _(RawOrigin::Signed(payer), merchant_lookup, currency_id, amount, 10u32.into(), Some(12))
+	verify {
+		assert!(Subscriptions::<T>::get(0).is_some());
+	}
+
+	cancel_subscription {
+		let currency_id = non_native_currency_id::<T>();
+		let payer: T::AccountId = whitelisted_caller();
+		fund_subscription_deposit::<T>(&payer);
+		let merchant: T::AccountId = account("merchant", 0, SEED);
+		Pallet::<T>::create_subscription(
+			RawOrigin::Signed(payer.clone()).into(),
+			T::Lookup::unlookup(merchant),
+			currency_id,
+			dollar::<T>(currency_id, 100),
+			10u32.into(),
+			None,
+		)?;
+	}: _(RawOrigin::Signed(payer), 0)
+	verify {
+		assert!(Subscriptions::<T>::get(0).is_none());
+	}
+
+	collect {
+		let currency_id = non_native_currency_id::<T>();
+		let amount = dollar::<T>(currency_id, 100);
+		let payer = funded_account::<T>("payer", 0, currency_id, dollar::<T>(currency_id, 1_000));
+		fund_subscription_deposit::<T>(&payer);
+		let merchant: T::AccountId = account("merchant", 0, SEED);
+		Pallet::<T>::create_subscription(
+			RawOrigin::Signed(payer).into(),
+			T::Lookup::unlookup(merchant.clone()),
+			currency_id,
+			amount,
+			10u32.into(),
+			None,
+		)?;
+	}: _(RawOrigin::Signed(merchant.clone()), 0)
+	verify {
+		assert_eq!(<Pallet<T> as Stp258Currency<T::AccountId>>::free_balance(currency_id, &merchant), amount);
+	}
+}
+
+impl_benchmark_test_suite!(
+	Pallet,
+	crate::mock::ExtBuilder::default().sett_n_jusd_registered().build(),
+	crate::mock::Runtime
+);
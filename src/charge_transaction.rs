@@ -0,0 +1,114 @@
+//! Payment of transaction fees in any currency of the module.
+
+use crate::{BalanceOf, Config, CurrencyIdOf, FeeCurrency, Pallet};
+use frame_support::traits::{ExistenceRequirement, Get, WithdrawReasons};
+use pallet_transaction_payment::OnChargeTransaction;
+use sp_runtime::{
+	traits::{DispatchInfoOf, PostDispatchInfoOf, Saturating, Zero},
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+};
+use sp_std::marker;
+
+/// Converts a fee in the native currency into another currency.
+pub trait FeePriceProvider<CurrencyId, Balance> {
+	/// The amount of `currency_id` worth `fee` of the native currency, or
+	/// `None` if fees cannot be paid in `currency_id`.
+	fn convert_native_fee(currency_id: CurrencyId, fee: Balance) -> Option<Balance>;
+}
+
+/// Charge transaction fees in the `FeeCurrency` of the signer, converted
+/// from the native fee by `PriceProvider`.
+///
+/// Fees fall back to the native currency if they cannot be converted into,
+/// or withdrawn from, the fee currency of the signer. Fees are burned and
+/// unused fees are refunded in the currency they were paid in.
+///
+/// Fees are not paid in a paused currency, nor in a currency the signer is
+/// frozen in, and fall back to the native currency instead. Fees never reap
+/// the signer, so that its nonce is kept, and are charged and refunded
+/// without events.
+pub struct Stp258ChargeTransaction<T, PriceProvider>(marker::PhantomData<(T, PriceProvider)>);
+
+impl<T, PriceProvider> Stp258ChargeTransaction<T, PriceProvider>
+where
+	T: Config,
+	PriceProvider: FeePriceProvider<CurrencyIdOf<T>, BalanceOf<T>>,
+{
+	fn convert_fee(currency_id: CurrencyIdOf<T>, fee: BalanceOf<T>) -> Option<BalanceOf<T>> {
+		if currency_id == T::GetStp258NativeId::get() {
+			Some(fee)
+		} else {
+			PriceProvider::convert_native_fee(currency_id, fee)
+		}
+	}
+
+	fn withdraw_in(
+		currency_id: CurrencyIdOf<T>,
+		who: &T::AccountId,
+		fee: BalanceOf<T>,
+		reasons: WithdrawReasons,
+	) -> Option<BalanceOf<T>> {
+		if <Pallet<T>>::is_paused(currency_id) || <Pallet<T>>::is_frozen(currency_id, who) {
+			return None;
+		}
+		let amount = Self::convert_fee(currency_id, fee)?;
+		<Pallet<T>>::withdraw_unchecked(currency_id, who, amount, reasons, ExistenceRequirement::KeepAlive)
+			.ok()
+			.map(|_| amount)
+	}
+}
+
+impl<T, PriceProvider> OnChargeTransaction<T> for Stp258ChargeTransaction<T, PriceProvider>
+where
+	T: Config + pallet_transaction_payment::Config,
+	PriceProvider: FeePriceProvider<CurrencyIdOf<T>, BalanceOf<T>>,
+{
+	type Balance = BalanceOf<T>;
+	type LiquidityInfo = Option<(CurrencyIdOf<T>, BalanceOf<T>)>;
+
+	fn withdraw_fee(
+		who: &T::AccountId,
+		_call: &T::Call,
+		_info: &DispatchInfoOf<T::Call>,
+		fee: Self::Balance,
+		tip: Self::Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		if fee.is_zero() {
+			return Ok(None);
+		}
+
+		let reasons = if tip.is_zero() {
+			WithdrawReasons::TRANSACTION_PAYMENT
+		} else {
+			WithdrawReasons::TRANSACTION_PAYMENT | WithdrawReasons::TIP
+		};
+		let native_currency_id = T::GetStp258NativeId::get();
+
+		FeeCurrency::<T>::get(who)
+			.and_then(|currency_id| {
+				Self::withdraw_in(currency_id, who, fee, reasons).map(|amount| (currency_id, amount))
+			})
+			.or_else(|| {
+				Self::withdraw_in(native_currency_id, who, fee, reasons).map(|amount| (native_currency_id, amount))
+			})
+			.map(Some)
+			.ok_or_else(|| InvalidTransaction::Payment.into())
+	}
+
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		_dispatch_info: &DispatchInfoOf<T::Call>,
+		_post_info: &PostDispatchInfoOf<T::Call>,
+		corrected_fee: Self::Balance,
+		_tip: Self::Balance,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		if let Some((currency_id, paid)) = already_withdrawn {
+			let corrected = Self::convert_fee(currency_id, corrected_fee).unwrap_or(paid).min(paid);
+			let refund = paid.saturating_sub(corrected);
+			<Pallet<T>>::deposit_unchecked(currency_id, who, refund)
+				.map_err(|_| TransactionValidityError::from(InvalidTransaction::Payment))?;
+		}
+		Ok(())
+	}
+}
@@ -1,13 +1,13 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
-use codec::Codec;
+use codec::{Codec, Decode, Encode};
 use frame_support::{
 	pallet_prelude::*,
 	traits::{
-		Currency as SetheumCurrency, ExistenceRequirement, Get, 
+		Currency as SetheumCurrency, ExistenceRequirement, GenesisBuild, Get, Imbalance, OnUnbalanced,
 		LockableCurrency as SetheumLockableCurrency,
-		ReservableCurrency as SetheumReservableCurrency, WithdrawReasons,
+		ReservableCurrency as SetheumReservableCurrency, SignedImbalance, WithdrawReasons,
 	},
 };
 use frame_system::{ensure_root, ensure_signed, pallet_prelude::*};
@@ -18,22 +18,168 @@ use serp_traits::{
 	LockIdentifier, Stp258Currency, Stp258CurrencyExtended, Stp258CurrencyReservable, Stp258CurrencyLockable,
 };
 use orml_utilities::with_transaction_result;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_runtime::{
-	traits::{CheckedSub, MaybeSerializeDeserialize, StaticLookup, Zero},
-	DispatchError, DispatchResult,
+	helpers_128bit::multiply_by_rational,
+	traits::{
+		AtLeast32BitUnsigned, Bounded, CheckedMul, CheckedSub, MaybeSerializeDeserialize, One, Saturating,
+		StaticLookup, UniqueSaturatedFrom, UniqueSaturatedInto, Zero,
+	},
+	DispatchError, DispatchResult, PerThing, Perbill, RuntimeDebug,
 };
 use sp_std::{
 	convert::{TryFrom, TryInto},
 	fmt::Debug,
 	marker, result,
+	vec::Vec,
 };
 
+mod benchmarking;
+mod charge_transaction;
 mod default_weight;
+mod imbalances;
 mod mock;
 mod tests;
 
+pub use charge_transaction::{FeePriceProvider, Stp258ChargeTransaction};
+pub use imbalances::{NegativeImbalance, PositiveImbalance};
 pub use module::*;
 
+/// Identifier of a named reserve, e.g. the module which made it.
+pub type ReserveIdentifier = [u8; 8];
+
+/// Identifier of a scheduled transfer.
+pub type ScheduledTransferId = u32;
+
+/// The named reserve holding the amounts of scheduled transfers.
+pub const SCHEDULED_TRANSFER_RESERVE_ID: ReserveIdentifier = *b"stp258st";
+
+/// Identifier of a subscription.
+pub type SubscriptionId = u32;
+
+/// The named reserve holding the deposits of subscriptions.
+pub const SUBSCRIPTION_RESERVE_ID: ReserveIdentifier = *b"stp258sb";
+
+/// Metadata of a currency, as shown by wallets and explorers.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CurrencyMetadata<Balance> {
+	/// Name of the currency, e.g. `Setheum Dollar`.
+	pub name: Vec<u8>,
+	/// Ticker symbol of the currency, e.g. `SETT`.
+	pub symbol: Vec<u8>,
+	/// Number of decimals, the base unit is `10^decimals`.
+	pub decimals: u8,
+	/// Whether the currency is a SERP stablecoin.
+	pub is_stablecoin: bool,
+	/// Minimum balance shown by wallets, if set. Display only: the
+	/// existential deposit of the underlying currency is what is enforced.
+	pub minimum_balance: Option<Balance>,
+}
+
+/// Fee schedule of transfers of a currency, paid by the sender on top of
+/// the amount transferred.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct TransferFee<Balance> {
+	/// Fee charged on every transfer.
+	pub flat: Balance,
+	/// Fee charged in proportion to the amount transferred.
+	pub rate: Perbill,
+	/// Lower bound of the fee.
+	pub min: Balance,
+	/// Upper bound of the fee.
+	pub max: Balance,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy> TransferFee<Balance> {
+	/// The fee for transferring `amount`.
+	pub fn fee(&self, amount: Balance) -> Balance {
+		self.flat
+			.saturating_add(self.rate * amount)
+			.max(self.min)
+			.min(self.max)
+	}
+}
+
+/// The lock identifier of vesting schedules.
+pub const VESTING_LOCK_ID: LockIdentifier = *b"stp258vs";
+
+/// A vesting schedule, releasing `per_period` every `period` blocks from
+/// `start`, `period_count` times.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct VestingSchedule<BlockNumber, Balance> {
+	/// Vesting starting block.
+	pub start: BlockNumber,
+	/// Number of blocks between vests.
+	pub period: BlockNumber,
+	/// Number of vests.
+	pub period_count: u32,
+	/// Amount of tokens to release per vest.
+	pub per_period: Balance,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy, Balance: AtLeast32BitUnsigned + Copy>
+	VestingSchedule<BlockNumber, Balance>
+{
+	/// The total amount of the schedule, `None` on overflow.
+	pub fn total_amount(&self) -> Option<Balance> {
+		self.per_period.checked_mul(&self.period_count.into())
+	}
+
+	/// The amount still locked at block `now`.
+	///
+	/// `period` must be non-zero and `total_amount` must not overflow.
+	pub fn locked_amount(&self, now: BlockNumber) -> Balance {
+		let vested = now.saturating_sub(self.start) / self.period;
+		let unvested = self.period_count.saturating_sub(vested.unique_saturated_into());
+		self.per_period.saturating_mul(unvested.into())
+	}
+}
+
+/// A transfer of `amount` of `currency_id` from `from` to `to`, executed at
+/// the start of block `at` out of the reserved balance of `from`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ScheduledTransfer<AccountId, CurrencyId, Balance, BlockNumber> {
+	/// The sender, whose balance is reserved until the transfer.
+	pub from: AccountId,
+	/// The beneficiary.
+	pub to: AccountId,
+	/// The currency transferred.
+	pub currency_id: CurrencyId,
+	/// The amount transferred.
+	pub amount: Balance,
+	/// The block at the start of which the transfer is executed.
+	pub at: BlockNumber,
+}
+
+/// A subscription, allowing `merchant` to pull `amount` of `currency_id`
+/// from `payer` every `period` blocks.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Subscription<AccountId, CurrencyId, Balance, BlockNumber> {
+	/// The account paying.
+	pub payer: AccountId,
+	/// The account collecting the payments.
+	pub merchant: AccountId,
+	/// The currency paid.
+	pub currency_id: CurrencyId,
+	/// The amount of each payment.
+	pub amount: Balance,
+	/// Number of blocks between payments.
+	pub period: BlockNumber,
+	/// The block from which the next payment can be collected.
+	pub next_due: BlockNumber,
+	/// Number of payments collected.
+	pub payments_made: u32,
+	/// Number of payments after which the subscription ends, if any.
+	pub max_payments: Option<u32>,
+	/// The deposit of the native currency reserved from the payer until the
+	/// subscription ends.
+	pub deposit: Balance,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -42,8 +188,35 @@ pub mod module {
 		fn transfer_non_native_currency() -> Weight;
 		fn transfer_native_currency() -> Weight;
 		fn update_balance_non_native_currency() -> Weight;
+		fn update_balance_non_native_currency_killing() -> Weight;
 		fn update_balance_native_currency_creating() -> Weight;
 		fn update_balance_native_currency_killing() -> Weight;
+		fn register_currency() -> Weight;
+		fn update_currency_metadata() -> Weight;
+		fn transfer_all() -> Weight;
+		fn transfer_batch(n: u32) -> Weight;
+		fn approve() -> Weight;
+		fn increase_allowance() -> Weight;
+		fn decrease_allowance() -> Weight;
+		fn transfer_from() -> Weight;
+		fn force_set_lock() -> Weight;
+		fn force_remove_lock() -> Weight;
+		fn pause_currency() -> Weight;
+		fn unpause_currency() -> Weight;
+		fn freeze_account() -> Weight;
+		fn thaw_account() -> Weight;
+		fn set_fee_currency() -> Weight;
+		fn set_transfer_fee() -> Weight;
+		fn charge_transfer_fee() -> Weight;
+		fn vested_transfer() -> Weight;
+		fn claim() -> Weight;
+		fn update_vesting_schedules(n: u32) -> Weight;
+		fn schedule_transfer() -> Weight;
+		fn cancel_scheduled_transfer() -> Weight;
+		fn execute_scheduled_transfers(n: u32) -> Weight;
+		fn create_subscription() -> Weight;
+		fn cancel_subscription() -> Weight;
+		fn collect() -> Weight;
 	}
 
 	pub(crate) type BalanceOf<T> =
@@ -52,6 +225,19 @@ pub mod module {
 		<<T as Config>::Stp258Currency as Stp258Currency<<T as frame_system::Config>::AccountId>>::CurrencyId;
 	pub(crate) type AmountOf<T> =
 		<<T as Config>::Stp258Currency as Stp258CurrencyExtended<<T as frame_system::Config>::AccountId>>::Amount;
+	pub(crate) type VestingScheduleOf<T> = VestingSchedule<<T as frame_system::Config>::BlockNumber, BalanceOf<T>>;
+	pub(crate) type ScheduledTransferOf<T> = ScheduledTransfer<
+		<T as frame_system::Config>::AccountId,
+		CurrencyIdOf<T>,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+	pub(crate) type SubscriptionOf<T> = Subscription<
+		<T as frame_system::Config>::AccountId,
+		CurrencyIdOf<T>,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+	>;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -63,12 +249,67 @@ pub mod module {
 			+ Stp258CurrencyReservable<Self::AccountId>;
 
 		type Stp258Native: Stp258AssetExtended<Self::AccountId, Balance = BalanceOf<Self>, Amount = AmountOf<Self>>
+			+ Stp258AssetExistential<Self::AccountId, Balance = BalanceOf<Self>>
 			+ Stp258AssetLockable<Self::AccountId, Balance = BalanceOf<Self>>
+			+ Stp258AssetWithdrawReasons<Self::AccountId, Balance = BalanceOf<Self>>
 			+ Stp258AssetReservable<Self::AccountId, Balance = BalanceOf<Self>>;
 
 		#[pallet::constant]
 		type GetStp258NativeId: Get<CurrencyIdOf<Self>>;
 
+		/// Reads the locks on the native currency.
+		type NativeLocks: Stp258AssetLocksInspect<Self::AccountId, BalanceOf<Self>>;
+
+		/// Reads the locks on currencies other than the native one.
+		type CurrencyLocks: Stp258CurrencyLocksInspect<Self::AccountId, CurrencyIdOf<Self>, BalanceOf<Self>>;
+
+		/// The origin which may update balances, e.g. a treasury council or
+		/// a SERP committee.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may pause and unpause transfers of a currency.
+		type PauseOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may freeze and thaw accounts, e.g. the issuer of
+		/// a regulated stablecoin.
+		type FreezeOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum number of transfers in a `transfer_batch` call.
+		#[pallet::constant]
+		type MaxBatchTransfers: Get<u32>;
+
+		/// The Serper account, which receives `GetSerperRatio` of transfer
+		/// fees.
+		#[pallet::constant]
+		type GetSerperAcc: Get<Self::AccountId>;
+
+		/// The SettPay account, which receives the rest of transfer fees.
+		#[pallet::constant]
+		type GetSettPayAcc: Get<Self::AccountId>;
+
+		/// The share of transfer fees paid to the Serper account.
+		#[pallet::constant]
+		type GetSerperRatio: Get<Perbill>;
+
+		/// The maximum number of vesting schedules of an account in a
+		/// currency.
+		#[pallet::constant]
+		type MaxVestingSchedules: Get<u32>;
+
+		/// The maximum number of locks the underlying currencies keep on an
+		/// account, reported by `SetheumCurrencyAdapter`.
+		#[pallet::constant]
+		type MaxLocks: Get<u32>;
+
+		/// The maximum number of transfers scheduled at a block.
+		#[pallet::constant]
+		type MaxScheduledPerBlock: Get<u32>;
+
+		/// The deposit of the native currency reserved from the payer of a
+		/// subscription, returned when the subscription ends.
+		#[pallet::constant]
+		type SubscriptionDeposit: Get<BalanceOf<Self>>;
+
 		/// Weight information for extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -79,78 +320,1487 @@ pub mod module {
 		AmountIntoBalanceFailed,
 		/// Balance is too low.
 		BalanceTooLow,
+		/// Transfer would kill the sender account but keep-alive was requested.
+		KeepAlive,
+		/// Too many transfers in a batch.
+		TooManyTransfers,
+		/// Amount exceeds the allowance of the spender.
+		AllowanceExceeded,
+		/// Transfers of the currency are paused.
+		CurrencyPaused,
+		/// The native currency pays transaction fees and cannot be paused.
+		CannotPauseNativeCurrency,
+		/// The account is frozen for the currency.
+		AccountFrozen,
+		/// The account has named reserves, which would not match its
+		/// reserved balance once merged.
+		NamedReservesExist,
+		/// The currency is already registered.
+		CurrencyAlreadyRegistered,
+		/// The currency is not registered.
+		CurrencyNotRegistered,
+		/// Beneficiary account must pre-exist.
+		DeadAccount,
+		/// The minimum of a transfer fee is above its maximum.
+		InvalidTransferFee,
+		/// Vesting period is zero.
+		ZeroVestingPeriod,
+		/// Number of vests is zero.
+		ZeroVestingPeriodCount,
+		/// Total amount of a vesting schedule overflows.
+		VestingOverflow,
+		/// Balance is too low to be locked by the vesting schedules.
+		InsufficientBalanceToLock,
+		/// Too many vesting schedules.
+		TooManyVestingSchedules,
+		/// The block of a scheduled transfer is not in the future.
+		ScheduledInPast,
+		/// Too many transfers scheduled at the block.
+		TooManyScheduledTransfers,
+		/// No scheduled transfer id is available.
+		NoAvailableScheduledTransferId,
+		/// The scheduled transfer does not exist.
+		ScheduledTransferNotFound,
+		/// Only the sender of a scheduled transfer may cancel it.
+		NotScheduledTransferSender,
+		/// The period or maximum number of payments of a subscription is zero.
+		InvalidSubscription,
+		/// The amount of a subscription is zero.
+		ZeroSubscriptionAmount,
+		/// The payer of a subscription cannot be its merchant.
+		SelfSubscription,
+		/// No subscription id is available.
+		NoAvailableSubscriptionId,
+		/// The subscription does not exist.
+		SubscriptionNotFound,
+		/// Only the payer or merchant of a subscription may cancel it.
+		NotSubscriptionParty,
+		/// Only the merchant of a subscription may collect its payments.
+		NotSubscriptionMerchant,
+		/// The next payment of the subscription is not due yet.
+		SubscriptionNotDue,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Currency transfer success. [currency_id, from, to, amount]
+		Transferred(CurrencyIdOf<T>, T::AccountId, T::AccountId, BalanceOf<T>),
+		/// Update balance success. [currency_id, who, amount]
+		BalanceUpdated(CurrencyIdOf<T>, T::AccountId, AmountOf<T>),
+		/// Deposit success. [currency_id, who, amount]
+		Deposited(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// Withdraw success. [currency_id, who, amount]
+		Withdrawn(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// Currency registered. [currency_id]
+		CurrencyRegistered(CurrencyIdOf<T>),
+		/// Currency metadata updated. [currency_id]
+		CurrencyMetadataUpdated(CurrencyIdOf<T>),
+		/// Allowance set. [currency_id, owner, spender, amount]
+		Approval(CurrencyIdOf<T>, T::AccountId, T::AccountId, BalanceOf<T>),
+		/// Lock set. [lock_id, currency_id, who, amount]
+		LockSet(LockIdentifier, CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// Lock extended. [lock_id, currency_id, who, amount]
+		LockExtended(LockIdentifier, CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// Lock removed. [lock_id, currency_id, who]
+		LockRemoved(LockIdentifier, CurrencyIdOf<T>, T::AccountId),
+		/// Some balance was reserved. [currency_id, who, amount]
+		Reserved(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// Some reserved balance was unreserved. [currency_id, who, amount]
+		Unreserved(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// Some reserved balance was slashed. [currency_id, who, amount]
+		ReserveSlashed(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// Some reserved balance was moved to another account. [currency_id,
+		/// from, to, amount, status]
+		ReserveRepatriated(CurrencyIdOf<T>, T::AccountId, T::AccountId, BalanceOf<T>, BalanceStatus),
+		/// Transfers of a currency paused. [currency_id]
+		CurrencyPaused(CurrencyIdOf<T>),
+		/// Transfers of a currency unpaused. [currency_id]
+		CurrencyUnpaused(CurrencyIdOf<T>),
+		/// Account frozen. [currency_id, who]
+		AccountFrozen(CurrencyIdOf<T>, T::AccountId),
+		/// Account thawed. [currency_id, who]
+		AccountThawed(CurrencyIdOf<T>, T::AccountId),
+		/// Fee currency of an account set, `None` for the native currency.
+		/// [who, currency_id]
+		FeeCurrencySet(T::AccountId, Option<CurrencyIdOf<T>>),
+		/// Transfer fee schedule of a currency set. [currency_id]
+		TransferFeeSet(CurrencyIdOf<T>),
+		/// Transfer fee charged. [currency_id, who, fee]
+		TransferFeeCharged(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// Vesting schedule added. [currency_id, from, to, schedule]
+		VestingScheduleAdded(CurrencyIdOf<T>, T::AccountId, T::AccountId, VestingScheduleOf<T>),
+		/// Vested balance claimed. [currency_id, who, locked_amount]
+		Claimed(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// Vesting schedules updated. [currency_id, who]
+		VestingSchedulesUpdated(CurrencyIdOf<T>, T::AccountId),
+		/// Transfer scheduled. [id, currency_id, from, to, amount, at]
+		TransferScheduled(
+			ScheduledTransferId,
+			CurrencyIdOf<T>,
+			T::AccountId,
+			T::AccountId,
+			BalanceOf<T>,
+			T::BlockNumber,
+		),
+		/// Scheduled transfer cancelled. [id]
+		ScheduledTransferCancelled(ScheduledTransferId),
+		/// Scheduled transfer executed. [id]
+		ScheduledTransferExecuted(ScheduledTransferId),
+		/// Scheduled transfer failed, its amount was unreserved. [id, error]
+		ScheduledTransferFailed(ScheduledTransferId, DispatchError),
+		/// Subscription created. [id, currency_id, payer, merchant, amount,
+		/// period]
+		SubscriptionCreated(
+			SubscriptionId,
+			CurrencyIdOf<T>,
+			T::AccountId,
+			T::AccountId,
+			BalanceOf<T>,
+			T::BlockNumber,
+		),
+		/// Subscription cancelled. [id, who]
+		SubscriptionCancelled(SubscriptionId, T::AccountId),
+		/// Subscription payment collected. [id, payments_made]
+		SubscriptionCollected(SubscriptionId, u32),
+		/// Subscription ended after its last payment. [id]
+		SubscriptionCompleted(SubscriptionId),
+	}
+
+	/// The metadata of registered currencies.
+	///
+	/// CurrencyRegistry: map CurrencyId => Option<CurrencyMetadata>
+	#[pallet::storage]
+	#[pallet::getter(fn currency_metadata)]
+	pub type CurrencyRegistry<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, CurrencyMetadata<BalanceOf<T>>, OptionQuery>;
+
+	/// The amounts owners allow spenders to transfer on their behalf.
+	///
+	/// Allowances: double_map AccountId, (AccountId, CurrencyId) => Balance
+	#[pallet::storage]
+	pub type Allowances<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		(T::AccountId, CurrencyIdOf<T>),
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// The currencies whose transfers are paused.
+	///
+	/// PausedCurrencies: map CurrencyId => Option<()>
+	#[pallet::storage]
+	pub type PausedCurrencies<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, (), OptionQuery>;
+
+	/// The accounts which cannot move their balance of a currency.
+	///
+	/// FrozenAccounts: double_map AccountId, CurrencyId => Option<()>
+	#[pallet::storage]
+	pub type FrozenAccounts<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, CurrencyIdOf<T>, (), OptionQuery>;
+
+	/// The named reserves of an account, each part of its anonymous reserve.
+	///
+	/// NamedReserves: double_map AccountId, (CurrencyId, ReserveIdentifier) => Balance
+	#[pallet::storage]
+	pub type NamedReserves<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		(CurrencyIdOf<T>, ReserveIdentifier),
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// The currency an account pays transaction fees in, if not the native
+	/// currency.
+	///
+	/// FeeCurrency: map AccountId => Option<CurrencyId>
+	#[pallet::storage]
+	pub type FeeCurrency<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, CurrencyIdOf<T>, OptionQuery>;
+
+	/// The fee schedules of transfers of currencies.
+	///
+	/// TransferFees: map CurrencyId => Option<TransferFee>
+	#[pallet::storage]
+	pub type TransferFees<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, TransferFee<BalanceOf<T>>, OptionQuery>;
+
+	/// The vesting schedules of accounts.
+	///
+	/// VestingSchedules: double_map CurrencyId, AccountId => Vec<VestingSchedule>
+	#[pallet::storage]
+	pub type VestingSchedules<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CurrencyIdOf<T>,
+		Blake2_128Concat,
+		T::AccountId,
+		Vec<VestingScheduleOf<T>>,
+		ValueQuery,
+	>;
+
+	/// The id of the next scheduled transfer.
+	///
+	/// NextScheduledTransferId: ScheduledTransferId
+	#[pallet::storage]
+	pub type NextScheduledTransferId<T: Config> = StorageValue<_, ScheduledTransferId, ValueQuery>;
+
+	/// The pending scheduled transfers.
+	///
+	/// ScheduledTransfers: map ScheduledTransferId => Option<ScheduledTransfer>
+	#[pallet::storage]
+	#[pallet::getter(fn scheduled_transfer)]
+	pub type ScheduledTransfers<T: Config> =
+		StorageMap<_, Twox64Concat, ScheduledTransferId, ScheduledTransferOf<T>, OptionQuery>;
+
+	/// The ids of the transfers scheduled at a block.
+	///
+	/// TransferAgenda: map BlockNumber => Vec<ScheduledTransferId>
+	#[pallet::storage]
+	pub type TransferAgenda<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, Vec<ScheduledTransferId>, ValueQuery>;
+
+	/// The id of the next subscription.
+	///
+	/// NextSubscriptionId: SubscriptionId
+	#[pallet::storage]
+	pub type NextSubscriptionId<T: Config> = StorageValue<_, SubscriptionId, ValueQuery>;
+
+	/// The active subscriptions.
+	///
+	/// Subscriptions: map SubscriptionId => Option<Subscription>
+	#[pallet::storage]
+	#[pallet::getter(fn subscription)]
+	pub type Subscriptions<T: Config> = StorageMap<_, Twox64Concat, SubscriptionId, SubscriptionOf<T>, OptionQuery>;
+
+	/// The change of total issuance held by live imbalances of
+	/// `SetheumCurrencyAdapter`, as an increase and a decrease, applied when the
+	/// imbalances are dropped.
+	///
+	/// PendingIssuance: map CurrencyId => (Balance, Balance)
+	#[pallet::storage]
+	pub type PendingIssuance<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, (BalanceOf<T>, BalanceOf<T>), ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub currencies: Vec<(CurrencyIdOf<T>, CurrencyMetadata<BalanceOf<T>>)>,
+		/// Vesting schedules as `(who, currency_id, start, period,
+		/// period_count, per_period)`.
+		///
+		/// Each account must already hold the balance its schedules lock, so
+		/// the genesis of the underlying currencies must be built before the
+		/// genesis of this module, i.e. they must come first in
+		/// `construct_runtime!`.
+		pub vesting: Vec<(T::AccountId, CurrencyIdOf<T>, T::BlockNumber, T::BlockNumber, u32, BalanceOf<T>)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			GenesisConfig {
+				currencies: Default::default(),
+				vesting: Default::default(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			self.currencies.iter().for_each(|(currency_id, metadata)| {
+				CurrencyRegistry::<T>::insert(currency_id, metadata);
+			});
+
+			self.vesting
+				.iter()
+				.for_each(|(who, currency_id, start, period, period_count, per_period)| {
+					let schedule = VestingSchedule {
+						start: *start,
+						period: *period,
+						period_count: *period_count,
+						per_period: *per_period,
+					};
+					VestingSchedules::<T>::append(currency_id, who, schedule);
+					let schedules = VestingSchedules::<T>::get(currency_id, who);
+					Pallet::<T>::ensure_valid_vesting_schedules(*currency_id, who, &schedules)
+						.expect("Invalid genesis vesting schedule or balance, build the currencies genesis first");
+					Pallet::<T>::update_vesting_lock(*currency_id, who).expect("Genesis vesting lock failed");
+				});
+		}
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let ids = TransferAgenda::<T>::take(now);
+			let count = ids.len() as u32;
+			ids.into_iter().for_each(Self::execute_scheduled_transfer);
+			T::WeightInfo::execute_scheduled_transfers(count)
+				.saturating_add(T::WeightInfo::charge_transfer_fee().saturating_mul(count as Weight))
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Transfer some balance to another account under `currency_id`.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(
+			T::WeightInfo::transfer_non_native_currency().saturating_add(T::WeightInfo::charge_transfer_fee())
+		)]
+		pub fn transfer(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			<Self as Stp258Currency<T::AccountId>>::transfer(currency_id, &from, &to, amount)?;
+			Ok(Some(Self::transfer_weight(currency_id, &from, &to, amount)).into())
+		}
+
+		/// Transfer some native currency to another account.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(
+			T::WeightInfo::transfer_native_currency().saturating_add(T::WeightInfo::charge_transfer_fee())
+		)]
+		pub fn transfer_native_currency(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			let currency_id = T::GetStp258NativeId::get();
+			Self::do_transfer(currency_id, &from, &to, amount, ExistenceRequirement::AllowDeath)?;
+			Ok(Some(Self::transfer_weight(currency_id, &from, &to, amount)).into())
+		}
+
+		/// Same as the `transfer` call, but fails if the transfer would
+		/// kill the sender account.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(
+			T::WeightInfo::transfer_non_native_currency().saturating_add(T::WeightInfo::charge_transfer_fee())
+		)]
+		pub fn transfer_keep_alive(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			Self::do_transfer(currency_id, &from, &to, amount, ExistenceRequirement::KeepAlive)?;
+			Ok(Some(Self::transfer_weight(currency_id, &from, &to, amount)).into())
+		}
+
+		/// Same as the `transfer_native_currency` call, but fails if the
+		/// transfer would kill the sender account.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(
+			T::WeightInfo::transfer_native_currency().saturating_add(T::WeightInfo::charge_transfer_fee())
+		)]
+		pub fn transfer_native_currency_keep_alive(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			let currency_id = T::GetStp258NativeId::get();
+			Self::do_transfer(currency_id, &from, &to, amount, ExistenceRequirement::KeepAlive)?;
+			Ok(Some(Self::transfer_weight(currency_id, &from, &to, amount)).into())
+		}
+
+		/// Transfer all transferable balance of `currency_id` to another
+		/// account.
+		///
+		/// Locked and reserved balances are left behind, as is the minimum
+		/// balance if `keep_alive` is set.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::transfer_all().saturating_add(T::WeightInfo::charge_transfer_fee()))]
+		pub fn transfer_all(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			keep_alive: bool,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			let amount = Self::amount_less_transfer_fee(
+				currency_id,
+				Self::transferable_balance(currency_id, &from, keep_alive),
+			);
+			let existence_requirement = if keep_alive {
+				ExistenceRequirement::KeepAlive
+			} else {
+				ExistenceRequirement::AllowDeath
+			};
+			Self::do_transfer(currency_id, &from, &to, amount, existence_requirement)?;
+			Ok(().into())
+		}
+
+		/// Transfer some balances, possibly of different currencies, to
+		/// several accounts at once.
+		///
+		/// Either all transfers succeed or none of them is applied.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(
+			T::WeightInfo::transfer_batch(transfers.len() as u32)
+				.saturating_add(T::WeightInfo::charge_transfer_fee().saturating_mul(transfers.len() as Weight))
+		)]
+		pub fn transfer_batch(
+			origin: OriginFor<T>,
+			transfers: Vec<(<T::Lookup as StaticLookup>::Source, CurrencyIdOf<T>, BalanceOf<T>)>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			ensure!(
+				transfers.len() as u32 <= T::MaxBatchTransfers::get(),
+				Error::<T>::TooManyTransfers
+			);
+			with_transaction_result(|| -> DispatchResult {
+				for (dest, currency_id, amount) in transfers {
+					let to = T::Lookup::lookup(dest)?;
+					Self::do_transfer(currency_id, &from, &to, amount, ExistenceRequirement::AllowDeath)?;
+				}
+				Ok(())
+			})?;
+			Ok(().into())
+		}
+
+		/// Allow `spender` to transfer up to `amount` of `currency_id` on
+		/// behalf of the caller, replacing any previous allowance.
+		///
+		/// The dispatch origin for this call must be `Signed` by the owner.
+		#[pallet::weight(T::WeightInfo::approve())]
+		pub fn approve(
+			origin: OriginFor<T>,
+			spender: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			let spender = T::Lookup::lookup(spender)?;
+			Self::set_allowance(currency_id, &owner, &spender, amount);
+			Ok(().into())
+		}
+
+		/// Increase the allowance of `spender` under `currency_id` by
+		/// `amount`.
+		///
+		/// The dispatch origin for this call must be `Signed` by the owner.
+		#[pallet::weight(T::WeightInfo::increase_allowance())]
+		pub fn increase_allowance(
+			origin: OriginFor<T>,
+			spender: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			let spender = T::Lookup::lookup(spender)?;
+			let allowance = Self::allowance(currency_id, &owner, &spender).saturating_add(amount);
+			Self::set_allowance(currency_id, &owner, &spender, allowance);
+			Ok(().into())
+		}
+
+		/// Decrease the allowance of `spender` under `currency_id` by
+		/// `amount`.
+		///
+		/// The dispatch origin for this call must be `Signed` by the owner.
+		#[pallet::weight(T::WeightInfo::decrease_allowance())]
+		pub fn decrease_allowance(
+			origin: OriginFor<T>,
+			spender: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			let spender = T::Lookup::lookup(spender)?;
+			let allowance = Self::allowance(currency_id, &owner, &spender)
+				.checked_sub(&amount)
+				.ok_or(Error::<T>::AllowanceExceeded)?;
+			Self::set_allowance(currency_id, &owner, &spender, allowance);
+			Ok(().into())
+		}
+
+		/// Transfer some balance of `owner` to another account, spending the
+		/// allowance of the caller on both the amount and its transfer fee.
+		///
+		/// The dispatch origin for this call must be `Signed` by the spender.
+		#[pallet::weight(T::WeightInfo::transfer_from().saturating_add(T::WeightInfo::charge_transfer_fee()))]
+		pub fn transfer_from(
+			origin: OriginFor<T>,
+			owner: <T::Lookup as StaticLookup>::Source,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let spender = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+			let to = T::Lookup::lookup(dest)?;
+			let fee = Self::transfer_fee(currency_id, amount);
+			let allowance = Self::allowance(currency_id, &owner, &spender)
+				.checked_sub(&amount.saturating_add(fee))
+				.ok_or(Error::<T>::AllowanceExceeded)?;
+			<Self as Stp258Currency<T::AccountId>>::transfer(currency_id, &owner, &to, amount)?;
+			Self::set_allowance(currency_id, &owner, &spender, allowance);
+			Ok(().into())
+		}
+
+		/// update amount of account `who` under `currency_id`.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(
+			T::WeightInfo::update_balance_non_native_currency()
+				.max(T::WeightInfo::update_balance_non_native_currency_killing())
+				.max(T::WeightInfo::update_balance_native_currency_creating())
+				.max(T::WeightInfo::update_balance_native_currency_killing())
+		)]
+		pub fn update_balance(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			amount: AmountOf<T>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let dest = T::Lookup::lookup(who)?;
+			if amount.is_zero() {
+				<Self as Stp258CurrencyExtended<T::AccountId>>::update_balance(currency_id, &dest, amount)?;
+				return Ok(Some(T::DbWeight::get().reads(1)).into());
+			}
+			let existed = !<Self as Stp258Currency<T::AccountId>>::total_balance(currency_id, &dest).is_zero();
+			<Self as Stp258CurrencyExtended<T::AccountId>>::update_balance(currency_id, &dest, amount)?;
+			let exists = !<Self as Stp258Currency<T::AccountId>>::total_balance(currency_id, &dest).is_zero();
+
+			Ok(Some(Self::update_balance_weight(currency_id, existed && !exists)).into())
+		}
+
+		/// Set a lock on the balance of `who` under `currency_id`, for
+		/// governance recovery.
+		///
+		/// The dispatch origin of this call must be _Root_.
+		#[pallet::weight(T::WeightInfo::force_set_lock())]
+		pub fn force_set_lock(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			lock_id: LockIdentifier,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			<Self as Stp258CurrencyLockable<T::AccountId>>::set_lock(lock_id, currency_id, &who, amount)?;
+			Ok(().into())
+		}
+
+		/// Remove a lock from the balance of `who` under `currency_id`, for
+		/// governance recovery.
+		///
+		/// The dispatch origin of this call must be _Root_.
+		#[pallet::weight(T::WeightInfo::force_remove_lock())]
+		pub fn force_remove_lock(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			lock_id: LockIdentifier,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			<Self as Stp258CurrencyLockable<T::AccountId>>::remove_lock(lock_id, currency_id, &who)?;
+			Ok(().into())
+		}
+
+		/// Pause transfers, deposits and withdrawals of `currency_id`, other
+		/// than the native currency.
+		///
+		/// The dispatch origin of this call must be `PauseOrigin`.
+		#[pallet::weight(T::WeightInfo::pause_currency())]
+		pub fn pause_currency(origin: OriginFor<T>, currency_id: CurrencyIdOf<T>) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(
+				currency_id != T::GetStp258NativeId::get(),
+				Error::<T>::CannotPauseNativeCurrency
+			);
+			PausedCurrencies::<T>::insert(currency_id, ());
+
+			Self::deposit_event(Event::CurrencyPaused(currency_id));
+			Ok(().into())
+		}
+
+		/// Unpause transfers, deposits and withdrawals of `currency_id`.
+		///
+		/// The dispatch origin of this call must be `PauseOrigin`.
+		#[pallet::weight(T::WeightInfo::unpause_currency())]
+		pub fn unpause_currency(origin: OriginFor<T>, currency_id: CurrencyIdOf<T>) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			PausedCurrencies::<T>::remove(currency_id);
+
+			Self::deposit_event(Event::CurrencyUnpaused(currency_id));
+			Ok(().into())
+		}
+
+		/// Freeze the balance of `who` under `currency_id`, so that it can
+		/// no longer be transferred, withdrawn or reserved.
+		///
+		/// The dispatch origin of this call must be `FreezeOrigin`.
+		#[pallet::weight(T::WeightInfo::freeze_account())]
+		pub fn freeze_account(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			T::FreezeOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			FrozenAccounts::<T>::insert(&who, currency_id, ());
+
+			Self::deposit_event(Event::AccountFrozen(currency_id, who));
+			Ok(().into())
+		}
+
+		/// Thaw the balance of `who` under `currency_id`.
+		///
+		/// The dispatch origin of this call must be `FreezeOrigin`.
+		#[pallet::weight(T::WeightInfo::thaw_account())]
+		pub fn thaw_account(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			T::FreezeOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			FrozenAccounts::<T>::remove(&who, currency_id);
+
+			Self::deposit_event(Event::AccountThawed(currency_id, who));
+			Ok(().into())
+		}
+
+		/// Register a new currency with its metadata.
+		///
+		/// The dispatch origin of this call must be _Root_.
+		#[pallet::weight(T::WeightInfo::register_currency())]
+		pub fn register_currency(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			metadata: CurrencyMetadata<BalanceOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(
+				!CurrencyRegistry::<T>::contains_key(currency_id),
+				Error::<T>::CurrencyAlreadyRegistered
+			);
+			CurrencyRegistry::<T>::insert(currency_id, metadata);
+
+			Self::deposit_event(Event::CurrencyRegistered(currency_id));
+			Ok(().into())
+		}
+
+		/// Update the metadata of a registered currency.
+		///
+		/// The dispatch origin of this call must be _Root_.
+		#[pallet::weight(T::WeightInfo::update_currency_metadata())]
+		pub fn update_currency_metadata(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			metadata: CurrencyMetadata<BalanceOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			CurrencyRegistry::<T>::try_mutate(currency_id, |maybe_metadata| -> DispatchResult {
+				let existing = maybe_metadata.as_mut().ok_or(Error::<T>::CurrencyNotRegistered)?;
+				*existing = metadata;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::CurrencyMetadataUpdated(currency_id));
+			Ok(().into())
+		}
+
+		/// Pay the transaction fees of the caller in `currency_id`, or in the
+		/// native currency if `None`.
+		///
+		/// Fees fall back to the native currency whenever they cannot be paid
+		/// in `currency_id`.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		#[pallet::weight(T::WeightInfo::set_fee_currency())]
+		pub fn set_fee_currency(
+			origin: OriginFor<T>,
+			currency_id: Option<CurrencyIdOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			match currency_id {
+				Some(currency_id) if currency_id != T::GetStp258NativeId::get() => {
+					ensure!(
+						CurrencyRegistry::<T>::contains_key(currency_id),
+						Error::<T>::CurrencyNotRegistered
+					);
+					FeeCurrency::<T>::insert(&who, currency_id);
+				}
+				_ => FeeCurrency::<T>::remove(&who),
+			}
+
+			Self::deposit_event(Event::FeeCurrencySet(who, currency_id));
+			Ok(().into())
+		}
+
+		/// Set the fee schedule of transfers of `currency_id`, or remove it
+		/// if `None`.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::set_transfer_fee())]
+		pub fn set_transfer_fee(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			fee: Option<TransferFee<BalanceOf<T>>>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			match fee {
+				Some(fee) => {
+					ensure!(fee.min <= fee.max, Error::<T>::InvalidTransferFee);
+					TransferFees::<T>::insert(currency_id, fee);
+				}
+				None => TransferFees::<T>::remove(currency_id),
+			}
+
+			Self::deposit_event(Event::TransferFeeSet(currency_id));
+			Ok(().into())
+		}
+
+		/// Transfer the total amount of `schedule` to `dest`, locked until it
+		/// vests.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::vested_transfer().saturating_add(T::WeightInfo::charge_transfer_fee()))]
+		pub fn vested_transfer(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			schedule: VestingScheduleOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+
+			let mut schedules = VestingSchedules::<T>::get(currency_id, &to);
+			ensure!(
+				(schedules.len() as u32) < T::MaxVestingSchedules::get(),
+				Error::<T>::TooManyVestingSchedules
+			);
+			let amount = Self::ensure_valid_vesting_schedule(&schedule)?;
+			schedules.push(schedule.clone());
+
+			with_transaction_result(|| {
+				Self::do_transfer(currency_id, &from, &to, amount, ExistenceRequirement::AllowDeath)?;
+				Self::ensure_valid_vesting_schedules(currency_id, &to, &schedules)?;
+				VestingSchedules::<T>::insert(currency_id, &to, schedules);
+				Self::update_vesting_lock(currency_id, &to)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::VestingScheduleAdded(currency_id, from, to, schedule));
+			Ok(().into())
+		}
+
+		/// Unlock the vested balance of the caller in `currency_id`.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::claim())]
+		pub fn claim(origin: OriginFor<T>, currency_id: CurrencyIdOf<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let locked_amount = Self::update_vesting_lock(currency_id, &who)?;
+
+			Self::deposit_event(Event::Claimed(currency_id, who, locked_amount));
+			Ok(().into())
+		}
+
+		/// Replace the vesting schedules of `who` in `currency_id`.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::update_vesting_schedules(schedules.len() as u32))]
+		pub fn update_vesting_schedules(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			schedules: Vec<VestingScheduleOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ensure!(
+				schedules.len() as u32 <= T::MaxVestingSchedules::get(),
+				Error::<T>::TooManyVestingSchedules
+			);
+			Self::ensure_valid_vesting_schedules(currency_id, &who, &schedules)?;
+
+			VestingSchedules::<T>::insert(currency_id, &who, schedules);
+			Self::update_vesting_lock(currency_id, &who)?;
+
+			Self::deposit_event(Event::VestingSchedulesUpdated(currency_id, who));
+			Ok(().into())
+		}
+
+		/// Schedule a transfer of `amount` of `currency_id` to `dest` at the
+		/// start of block `at_block`, reserving the amount until then.
+		///
+		/// The dispatch origin of this call must be _Signed_ by the
+		/// transactor.
+		#[pallet::weight(T::WeightInfo::schedule_transfer())]
+		pub fn schedule_transfer(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			at_block: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			ensure!(
+				at_block > <frame_system::Module<T>>::block_number(),
+				Error::<T>::ScheduledInPast
+			);
+			let mut agenda = TransferAgenda::<T>::get(at_block);
+			ensure!(
+				(agenda.len() as u32) < T::MaxScheduledPerBlock::get(),
+				Error::<T>::TooManyScheduledTransfers
+			);
+			let id = NextScheduledTransferId::<T>::get();
+			let next_id = id
+				.checked_add(1)
+				.ok_or(Error::<T>::NoAvailableScheduledTransferId)?;
+
+			<Self as Stp258CurrencyNamedReservable<T::AccountId>>::reserve_named(
+				&SCHEDULED_TRANSFER_RESERVE_ID,
+				currency_id,
+				&from,
+				amount,
+			)?;
+
+			NextScheduledTransferId::<T>::put(next_id);
+			agenda.push(id);
+			TransferAgenda::<T>::insert(at_block, agenda);
+			ScheduledTransfers::<T>::insert(
+				id,
+				ScheduledTransfer {
+					from: from.clone(),
+					to: to.clone(),
+					currency_id,
+					amount,
+					at: at_block,
+				},
+			);
+
+			Self::deposit_event(Event::TransferScheduled(id, currency_id, from, to, amount, at_block));
+			Ok(().into())
+		}
+
+		/// Cancel the scheduled transfer `id`, unreserving its amount.
+		///
+		/// The dispatch origin of this call must be _Signed_ by the sender
+		/// of the scheduled transfer.
+		#[pallet::weight(T::WeightInfo::cancel_scheduled_transfer())]
+		pub fn cancel_scheduled_transfer(origin: OriginFor<T>, id: ScheduledTransferId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let transfer = ScheduledTransfers::<T>::get(id).ok_or(Error::<T>::ScheduledTransferNotFound)?;
+			ensure!(transfer.from == who, Error::<T>::NotScheduledTransferSender);
+
+			ScheduledTransfers::<T>::remove(id);
+			let mut agenda = TransferAgenda::<T>::get(transfer.at);
+			agenda.retain(|scheduled| *scheduled != id);
+			if agenda.is_empty() {
+				TransferAgenda::<T>::remove(transfer.at);
+			} else {
+				TransferAgenda::<T>::insert(transfer.at, agenda);
+			}
+			<Self as Stp258CurrencyNamedReservable<T::AccountId>>::unreserve_named(
+				&SCHEDULED_TRANSFER_RESERVE_ID,
+				transfer.currency_id,
+				&who,
+				transfer.amount,
+			);
+
+			Self::deposit_event(Event::ScheduledTransferCancelled(id));
+			Ok(().into())
+		}
+
+		/// Allow `merchant` to collect `amount` of `currency_id` from the
+		/// caller every `period` blocks, from the current block on, at most
+		/// `max_payments` times if given.
+		///
+		/// `SubscriptionDeposit` of the native currency is reserved from the
+		/// caller until the subscription ends.
+		///
+		/// The dispatch origin of this call must be _Signed_ by the payer.
+		#[pallet::weight(T::WeightInfo::create_subscription())]
+		pub fn create_subscription(
+			origin: OriginFor<T>,
+			merchant: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			period: T::BlockNumber,
+			max_payments: Option<u32>,
+		) -> DispatchResultWithPostInfo {
+			let payer = ensure_signed(origin)?;
+			let merchant = T::Lookup::lookup(merchant)?;
+			ensure!(
+				!period.is_zero() && max_payments != Some(0),
+				Error::<T>::InvalidSubscription
+			);
+			ensure!(!amount.is_zero(), Error::<T>::ZeroSubscriptionAmount);
+			ensure!(payer != merchant, Error::<T>::SelfSubscription);
+
+			let id = NextSubscriptionId::<T>::get();
+			let next_id = id.checked_add(1).ok_or(Error::<T>::NoAvailableSubscriptionId)?;
+			let deposit = T::SubscriptionDeposit::get();
+			<Self as Stp258CurrencyNamedReservable<T::AccountId>>::reserve_named(
+				&SUBSCRIPTION_RESERVE_ID,
+				T::GetStp258NativeId::get(),
+				&payer,
+				deposit,
+			)?;
+			NextSubscriptionId::<T>::put(next_id);
+			Subscriptions::<T>::insert(
+				id,
+				Subscription {
+					payer: payer.clone(),
+					merchant: merchant.clone(),
+					currency_id,
+					amount,
+					period,
+					next_due: <frame_system::Module<T>>::block_number(),
+					payments_made: 0,
+					max_payments,
+					deposit,
+				},
+			);
+
+			Self::deposit_event(Event::SubscriptionCreated(id, currency_id, payer, merchant, amount, period));
+			Ok(().into())
+		}
+
+		/// Cancel the subscription `id`, returning its deposit to the payer.
+		///
+		/// The dispatch origin of this call must be _Signed_ by the payer or
+		/// the merchant of the subscription.
+		#[pallet::weight(T::WeightInfo::cancel_subscription())]
+		pub fn cancel_subscription(origin: OriginFor<T>, id: SubscriptionId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let subscription = Subscriptions::<T>::get(id).ok_or(Error::<T>::SubscriptionNotFound)?;
+			ensure!(
+				who == subscription.payer || who == subscription.merchant,
+				Error::<T>::NotSubscriptionParty
+			);
+
+			Self::end_subscription(id, &subscription);
+
+			Self::deposit_event(Event::SubscriptionCancelled(id, who));
+			Ok(().into())
+		}
+
+		/// Collect the next payment of the subscription `id`, once due.
+		/// Payments of periods which already ended are skipped.
+		///
+		/// The dispatch origin of this call must be _Signed_ by the merchant
+		/// of the subscription.
+		#[pallet::weight(T::WeightInfo::collect().saturating_add(T::WeightInfo::charge_transfer_fee()))]
+		pub fn collect(origin: OriginFor<T>, id: SubscriptionId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let mut subscription = Subscriptions::<T>::get(id).ok_or(Error::<T>::SubscriptionNotFound)?;
+			ensure!(who == subscription.merchant, Error::<T>::NotSubscriptionMerchant);
+			let now = <frame_system::Module<T>>::block_number();
+			ensure!(subscription.next_due <= now, Error::<T>::SubscriptionNotDue);
+
+			Self::do_transfer(
+				subscription.currency_id,
+				&subscription.payer,
+				&subscription.merchant,
+				subscription.amount,
+				ExistenceRequirement::AllowDeath,
+			)?;
+
+			subscription.payments_made = subscription.payments_made.saturating_add(1);
+			let periods = (now - subscription.next_due) / subscription.period + One::one();
+			subscription.next_due = subscription
+				.next_due
+				.saturating_add(subscription.period.saturating_mul(periods));
+			let payments_made = subscription.payments_made;
+			Self::deposit_event(Event::SubscriptionCollected(id, payments_made));
+
+			if subscription.max_payments == Some(payments_made) {
+				Self::end_subscription(id, &subscription);
+				Self::deposit_event(Event::SubscriptionCompleted(id));
+			} else {
+				Subscriptions::<T>::insert(id, subscription);
+			}
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Transfer `amount` of `currency_id` from `from` to `to`, reaping `from`
+	/// only if `existence_requirement` allows it.
+	pub(crate) fn do_transfer(
+		currency_id: CurrencyIdOf<T>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: BalanceOf<T>,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		Self::ensure_not_paused(currency_id)?;
+		Self::ensure_not_frozen(currency_id, from)?;
+		if amount.is_zero() || from == to {
+			return Ok(());
+		}
+		with_transaction_result(|| {
+			Self::transfer_unchecked(currency_id, from, to, amount, existence_requirement)?;
+			Self::deposit_event(Event::Transferred(currency_id, from.clone(), to.clone(), amount));
+			Self::charge_transfer_fee(currency_id, from, amount, existence_requirement)
+		})
+	}
+
+	/// Transfer `amount` of `currency_id` from `from` to `to` in the
+	/// underlying currency, without checking pause and freeze flags or
+	/// charging fees.
+	fn transfer_unchecked(
+		currency_id: CurrencyIdOf<T>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: BalanceOf<T>,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		if amount.is_zero() || from == to {
+			return Ok(());
+		}
+		if currency_id == T::GetStp258NativeId::get() {
+			T::Stp258Native::transfer_with_existence(from, to, amount, existence_requirement)
+		} else {
+			if existence_requirement == ExistenceRequirement::KeepAlive {
+				let remaining = T::Stp258Currency::total_balance(currency_id, from).saturating_sub(amount);
+				ensure!(remaining >= T::Stp258Currency::minimum_balance(currency_id), Error::<T>::KeepAlive);
+			}
+			T::Stp258Currency::transfer(currency_id, from, to, amount)
+		}
+	}
+
+	/// Withdraw `amount` of `currency_id` from `who` for `reasons` in the
+	/// underlying currency, without checking pause and freeze flags or
+	/// emitting events.
+	pub(crate) fn withdraw_unchecked(
+		currency_id: CurrencyIdOf<T>,
+		who: &T::AccountId,
+		amount: BalanceOf<T>,
+		reasons: WithdrawReasons,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+		if currency_id == T::GetStp258NativeId::get() {
+			T::Stp258Native::withdraw_with_reasons(who, amount, reasons, existence_requirement)
+		} else {
+			if existence_requirement == ExistenceRequirement::KeepAlive {
+				let remaining = T::Stp258Currency::total_balance(currency_id, who).saturating_sub(amount);
+				ensure!(remaining >= T::Stp258Currency::minimum_balance(currency_id), Error::<T>::KeepAlive);
+			}
+			T::Stp258Currency::withdraw(currency_id, who, amount)
+		}
+	}
+
+	/// Deposit `amount` of `currency_id` to `who` in the underlying
+	/// currency, without checking pause flags or emitting events.
+	pub(crate) fn deposit_unchecked(
+		currency_id: CurrencyIdOf<T>,
+		who: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+		if currency_id == T::GetStp258NativeId::get() {
+			T::Stp258Native::deposit(who, amount)
+		} else {
+			T::Stp258Currency::deposit(currency_id, who, amount)
+		}
+	}
+
+	/// The total issuance of `currency_id`, including the change held by live
+	/// imbalances of `SetheumCurrencyAdapter`.
+	pub(crate) fn adapted_total_issuance(currency_id: CurrencyIdOf<T>) -> BalanceOf<T> {
+		let (increase, decrease) = PendingIssuance::<T>::get(currency_id);
+		<Self as Stp258Currency<T::AccountId>>::total_issuance(currency_id)
+			.saturating_add(increase)
+			.saturating_sub(decrease)
+	}
+
+	/// Raise the total issuance of `currency_id` reported by
+	/// `SetheumCurrencyAdapter` by `amount`.
+	pub(crate) fn raise_pending_issuance(currency_id: CurrencyIdOf<T>, amount: BalanceOf<T>) {
+		if amount.is_zero() {
+			return;
+		}
+		let (increase, decrease) = PendingIssuance::<T>::get(currency_id);
+		let pending = if decrease >= amount {
+			(increase, decrease - amount)
+		} else {
+			(increase.saturating_add(amount - decrease), Zero::zero())
+		};
+		Self::set_pending_issuance(currency_id, pending);
+	}
+
+	/// Lower the total issuance of `currency_id` reported by
+	/// `SetheumCurrencyAdapter` by `amount`.
+	pub(crate) fn lower_pending_issuance(currency_id: CurrencyIdOf<T>, amount: BalanceOf<T>) {
+		if amount.is_zero() {
+			return;
+		}
+		let (increase, decrease) = PendingIssuance::<T>::get(currency_id);
+		let pending = if increase >= amount {
+			(increase - amount, decrease)
+		} else {
+			(Zero::zero(), decrease.saturating_add(amount - increase))
+		};
+		Self::set_pending_issuance(currency_id, pending);
+	}
+
+	fn set_pending_issuance(currency_id: CurrencyIdOf<T>, pending: (BalanceOf<T>, BalanceOf<T>)) {
+		if pending.0.is_zero() && pending.1.is_zero() {
+			PendingIssuance::<T>::remove(currency_id);
+		} else {
+			PendingIssuance::<T>::insert(currency_id, pending);
+		}
+	}
+
+	/// The fee for transferring `amount` of `currency_id`.
+	pub fn transfer_fee(currency_id: CurrencyIdOf<T>, amount: BalanceOf<T>) -> BalanceOf<T> {
+		TransferFees::<T>::get(currency_id).map_or_else(Zero::zero, |fee| fee.fee(amount))
+	}
+
+	/// Charge `who` the fee for transferring `amount` of `currency_id`,
+	/// split between the Serper and SettPay accounts by `GetSerperRatio`.
+	///
+	/// The fee is withdrawn from `who` and deposited to the Serper and
+	/// SettPay accounts, so that a share below the minimum balance of an
+	/// account which does not exist yet is dropped like dust instead of
+	/// failing the transfer.
+	pub(crate) fn charge_transfer_fee(
+		currency_id: CurrencyIdOf<T>,
+		who: &T::AccountId,
+		amount: BalanceOf<T>,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		let fee = Self::transfer_fee(currency_id, amount);
+		if fee.is_zero() {
+			return Ok(());
+		}
+
+		let serper_fee = T::GetSerperRatio::get() * fee;
+		Self::withdraw_unchecked(currency_id, who, fee, WithdrawReasons::TRANSFER, existence_requirement)?;
+		Self::deposit_unchecked(currency_id, &T::GetSerperAcc::get(), serper_fee)?;
+		Self::deposit_unchecked(currency_id, &T::GetSettPayAcc::get(), fee - serper_fee)?;
+
+		Self::deposit_event(Event::TransferFeeCharged(currency_id, who.clone(), fee));
+		Ok(())
+	}
+
+	/// Ensure `schedule` has non-zero period and period count and a total
+	/// amount which does not overflow, returning the total amount.
+	fn ensure_valid_vesting_schedule(
+		schedule: &VestingScheduleOf<T>,
+	) -> result::Result<BalanceOf<T>, DispatchError> {
+		ensure!(!schedule.period.is_zero(), Error::<T>::ZeroVestingPeriod);
+		ensure!(!schedule.period_count.is_zero(), Error::<T>::ZeroVestingPeriodCount);
+		Ok(schedule.total_amount().ok_or(Error::<T>::VestingOverflow)?)
+	}
+
+	/// Ensure every schedule of `schedules` is valid and `who` has the
+	/// balance of `currency_id` they still lock.
+	pub(crate) fn ensure_valid_vesting_schedules(
+		currency_id: CurrencyIdOf<T>,
+		who: &T::AccountId,
+		schedules: &[VestingScheduleOf<T>],
+	) -> DispatchResult {
+		let now = <frame_system::Module<T>>::block_number();
+		let mut locked_amount: BalanceOf<T> = Zero::zero();
+		for schedule in schedules {
+			Self::ensure_valid_vesting_schedule(schedule)?;
+			locked_amount = locked_amount.saturating_add(schedule.locked_amount(now));
+		}
+		ensure!(
+			<Self as Stp258Currency<T::AccountId>>::free_balance(currency_id, who) >= locked_amount,
+			Error::<T>::InsufficientBalanceToLock
+		);
+		Ok(())
+	}
+
+	/// Drop the fully vested schedules of `who` in `currency_id` and lock
+	/// the balance still vesting, returning the locked amount.
+	pub(crate) fn update_vesting_lock(
+		currency_id: CurrencyIdOf<T>,
+		who: &T::AccountId,
+	) -> result::Result<BalanceOf<T>, DispatchError> {
+		let now = <frame_system::Module<T>>::block_number();
+		let mut schedules = VestingSchedules::<T>::get(currency_id, who);
+		// a vesting lock is set exactly while `who` has schedules
+		let locked = !schedules.is_empty();
+		schedules.retain(|schedule| !schedule.locked_amount(now).is_zero());
+		let locked_amount = schedules
+			.iter()
+			.fold(Zero::zero(), |total: BalanceOf<T>, schedule| {
+				total.saturating_add(schedule.locked_amount(now))
+			});
+
+		if schedules.is_empty() {
+			if locked {
+				VestingSchedules::<T>::remove(currency_id, who);
+				<Self as Stp258CurrencyLockable<T::AccountId>>::remove_lock(VESTING_LOCK_ID, currency_id, who)?;
+			}
+		} else {
+			VestingSchedules::<T>::insert(currency_id, who, schedules);
+			<Self as Stp258CurrencyLockable<T::AccountId>>::set_lock(VESTING_LOCK_ID, currency_id, who, locked_amount)?;
+		}
+		Ok(locked_amount)
+	}
+
+	/// Remove the subscription `id` and return its deposit to the payer.
+	fn end_subscription(id: SubscriptionId, subscription: &SubscriptionOf<T>) {
+		Subscriptions::<T>::remove(id);
+		<Self as Stp258CurrencyNamedReservable<T::AccountId>>::unreserve_named(
+			&SUBSCRIPTION_RESERVE_ID,
+			T::GetStp258NativeId::get(),
+			&subscription.payer,
+			subscription.deposit,
+		);
+	}
+
+	/// Unreserve the amount of the scheduled transfer `id` and transfer it,
+	/// leaving it unreserved with the sender if the transfer fails.
+	///
+	/// Only the amount still reserved is transferred, so that a reserve
+	/// slashed or repatriated in the meantime is not made up from the free
+	/// balance of the sender.
+	fn execute_scheduled_transfer(id: ScheduledTransferId) {
+		if let Some(transfer) = ScheduledTransfers::<T>::take(id) {
+			let remaining = <Self as Stp258CurrencyNamedReservable<T::AccountId>>::unreserve_named(
+				&SCHEDULED_TRANSFER_RESERVE_ID,
+				transfer.currency_id,
+				&transfer.from,
+				transfer.amount,
+			);
+			let event = match Self::do_transfer(
+				transfer.currency_id,
+				&transfer.from,
+				&transfer.to,
+				transfer.amount - remaining,
+				ExistenceRequirement::AllowDeath,
+			) {
+				Ok(()) => Event::ScheduledTransferExecuted(id),
+				Err(e) => Event::ScheduledTransferFailed(id, e),
+			};
+			Self::deposit_event(event);
+		}
+	}
+
+	/// The largest amount of `currency_id` which can be transferred out of
+	/// `available` together with its transfer fee.
+	///
+	/// The fee is the proportional fee clamped between `min` and `max`, so
+	/// the amount must leave room for `min`, and for either the proportional
+	/// fee or `max`.
+	fn amount_less_transfer_fee(currency_id: CurrencyIdOf<T>, available: BalanceOf<T>) -> BalanceOf<T> {
+		let fee = match TransferFees::<T>::get(currency_id) {
+			Some(fee) => fee,
+			None => return available,
+		};
+		let one: BalanceOf<T> = One::one();
+
+		// the largest amount whose proportional fee is affordable, estimated as
+		// `(available - flat) / (1 + rate)` and corrected for the rounding of
+		// `rate * amount`
+		let proportional_fits =
+			|amount: BalanceOf<T>| amount.saturating_add(fee.flat).saturating_add(fee.rate * amount) <= available;
+		let mut proportional = if fee.flat > available {
+			Zero::zero()
+		} else {
+			let parts = fee.rate.deconstruct() as u128;
+			let accuracy = Perbill::ACCURACY as u128;
+			multiply_by_rational((available - fee.flat).unique_saturated_into(), accuracy, accuracy + parts)
+				.map(BalanceOf::<T>::unique_saturated_from)
+				.unwrap_or_else(|_| Zero::zero())
+		};
+		while proportional < available && proportional_fits(proportional + one) {
+			proportional += one;
+		}
+		while !proportional.is_zero() && !proportional_fits(proportional) {
+			proportional -= one;
+		}
+
+		let capped = if available >= fee.max {
+			available - fee.max
+		} else {
+			Zero::zero()
+		};
+		let amount = proportional.max(capped).min(available.saturating_sub(fee.min));
+		if amount.saturating_add(fee.fee(amount)) <= available {
+			amount
+		} else {
+			Zero::zero()
+		}
+	}
+
+	/// The actual weight of a transfer call, which only checks the pause
+	/// and freeze flags for zero amounts and self-transfers, and only pays
+	/// the fee legs if the transfer is charged a fee.
+	fn transfer_weight(
+		currency_id: CurrencyIdOf<T>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> Weight {
+		if amount.is_zero() || from == to {
+			return T::DbWeight::get().reads(2);
+		}
+		let weight = if currency_id == T::GetStp258NativeId::get() {
+			T::WeightInfo::transfer_native_currency()
+		} else {
+			T::WeightInfo::transfer_non_native_currency()
+		};
+		if Self::transfer_fee(currency_id, amount).is_zero() {
+			weight
+		} else {
+			weight.saturating_add(T::WeightInfo::charge_transfer_fee())
+		}
+	}
+
+	/// The actual weight of a non-zero `update_balance` call, the base
+	/// weight of the currency unless the call killed the account.
+	fn update_balance_weight(currency_id: CurrencyIdOf<T>, killed: bool) -> Weight {
+		match (currency_id == T::GetStp258NativeId::get(), killed) {
+			(false, false) => T::WeightInfo::update_balance_non_native_currency(),
+			(false, true) => T::WeightInfo::update_balance_non_native_currency_killing(),
+			(true, false) => T::WeightInfo::update_balance_native_currency_creating(),
+			(true, true) => T::WeightInfo::update_balance_native_currency_killing(),
+		}
+	}
+
+	/// The `(lock_id, amount)` locks on the balance of `who` in
+	/// `currency_id`.
+	pub fn locks(currency_id: CurrencyIdOf<T>, who: &T::AccountId) -> Vec<(LockIdentifier, BalanceOf<T>)> {
+		if currency_id == T::GetStp258NativeId::get() {
+			T::NativeLocks::locks(who)
+		} else {
+			T::CurrencyLocks::locks(currency_id, who)
+		}
 	}
 
-	#[pallet::event]
-	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
-	pub enum Event<T: Config> {
-		/// Currency transfer success. [currency_id, from, to, amount]
-		Transferred(CurrencyIdOf<T>, T::AccountId, T::AccountId, BalanceOf<T>),
-		/// Update balance success. [currency_id, who, amount]
-		BalanceUpdated(CurrencyIdOf<T>, T::AccountId, AmountOf<T>),
-		/// Deposit success. [currency_id, who, amount]
-		Deposited(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
-		/// Withdraw success. [currency_id, who, amount]
-		Withdrawn(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
+	/// The `(currency_id, free, reserved)` balances of `who` in the native
+	/// currency and every registered currency.
+	pub fn all_balances(who: &T::AccountId) -> Vec<(CurrencyIdOf<T>, BalanceOf<T>, BalanceOf<T>)> {
+		let native_currency_id = T::GetStp258NativeId::get();
+		sp_std::iter::once(native_currency_id)
+			.chain(
+				CurrencyRegistry::<T>::iter()
+					.map(|(currency_id, _)| currency_id)
+					.filter(|currency_id| *currency_id != native_currency_id),
+			)
+			.map(|currency_id| {
+				(
+					currency_id,
+					<Self as Stp258Currency<T::AccountId>>::free_balance(currency_id, who),
+					<Self as Stp258CurrencyReservable<T::AccountId>>::reserved_balance(currency_id, who),
+				)
+			})
+			.collect()
+	}
+
+	/// Whether transfers of `currency_id` are paused.
+	pub fn is_paused(currency_id: CurrencyIdOf<T>) -> bool {
+		PausedCurrencies::<T>::contains_key(currency_id)
+	}
+
+	fn ensure_not_paused(currency_id: CurrencyIdOf<T>) -> DispatchResult {
+		ensure!(!Self::is_paused(currency_id), Error::<T>::CurrencyPaused);
+		Ok(())
 	}
 
-	#[pallet::pallet]
-	pub struct Pallet<T>(PhantomData<T>);
+	/// Whether `who` is frozen for `currency_id`.
+	pub fn is_frozen(currency_id: CurrencyIdOf<T>, who: &T::AccountId) -> bool {
+		FrozenAccounts::<T>::contains_key(who, currency_id)
+	}
 
-	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	fn ensure_not_frozen(currency_id: CurrencyIdOf<T>, who: &T::AccountId) -> DispatchResult {
+		ensure!(!Self::is_frozen(currency_id, who), Error::<T>::AccountFrozen);
+		Ok(())
+	}
 
-	#[pallet::call]
-	impl<T: Config> Pallet<T> {
-		/// Transfer some balance to another account under `currency_id`.
-		///
-		/// The dispatch origin for this call must be `Signed` by the
-		/// transactor.
-		#[pallet::weight(T::WeightInfo::transfer_non_native_currency())]
-		pub fn transfer(
-			origin: OriginFor<T>,
-			dest: <T::Lookup as StaticLookup>::Source,
-			currency_id: CurrencyIdOf<T>,
-			#[pallet::compact] amount: BalanceOf<T>,
-		) -> DispatchResultWithPostInfo {
-			let from = ensure_signed(origin)?;
-			let to = T::Lookup::lookup(dest)?;
-			<Self as Stp258Currency<T::AccountId>>::transfer(currency_id, &from, &to, amount)?;
-			Ok(().into())
-		}
+	/// The amount of `currency_id` that `spender` may transfer on behalf of
+	/// `owner`.
+	pub fn allowance(currency_id: CurrencyIdOf<T>, owner: &T::AccountId, spender: &T::AccountId) -> BalanceOf<T> {
+		Allowances::<T>::get(owner, (spender, currency_id))
+	}
 
-		/// Transfer some native currency to another account.
-		///
-		/// The dispatch origin for this call must be `Signed` by the
-		/// transactor.
-		#[pallet::weight(T::WeightInfo::transfer_native_currency())]
-		pub fn transfer_native_currency(
-			origin: OriginFor<T>,
-			dest: <T::Lookup as StaticLookup>::Source,
-			#[pallet::compact] amount: BalanceOf<T>,
-		) -> DispatchResultWithPostInfo {
-			let from = ensure_signed(origin)?;
-			let to = T::Lookup::lookup(dest)?;
-			T::Stp258Native::transfer(&from, &to, amount)?;
+	fn set_allowance(currency_id: CurrencyIdOf<T>, owner: &T::AccountId, spender: &T::AccountId, amount: BalanceOf<T>) {
+		if amount.is_zero() {
+			Allowances::<T>::remove(owner, (spender, currency_id));
+		} else {
+			Allowances::<T>::insert(owner, (spender, currency_id), amount);
+		}
+		Self::deposit_event(Event::Approval(currency_id, owner.clone(), spender.clone(), amount));
+	}
 
-			Self::deposit_event(Event::Transferred(T::GetStp258NativeId::get(), from, to, amount));
-			Ok(().into())
+	fn set_named_reserve(
+		id: &ReserveIdentifier,
+		currency_id: CurrencyIdOf<T>,
+		who: &T::AccountId,
+		amount: BalanceOf<T>,
+	) {
+		if amount.is_zero() {
+			NamedReserves::<T>::remove(who, (currency_id, *id));
+		} else {
+			NamedReserves::<T>::insert(who, (currency_id, *id), amount);
 		}
+	}
 
-		/// update amount of account `who` under `currency_id`.
-		///
-		/// The dispatch origin of this call must be _Root_.
-		#[pallet::weight(T::WeightInfo::update_balance_non_native_currency())]
-		pub fn update_balance(
-			origin: OriginFor<T>,
-			who: <T::Lookup as StaticLookup>::Source,
-			currency_id: CurrencyIdOf<T>,
-			amount: AmountOf<T>,
-		) -> DispatchResultWithPostInfo {
-			ensure_root(origin)?;
-			let dest = T::Lookup::lookup(who)?;
-			<Self as Stp258CurrencyExtended<T::AccountId>>::update_balance(currency_id, &dest, amount)?;
-			Ok(().into())
+	/// The part of the free balance of `who` under `currency_id` that can be
+	/// withdrawn without breaking any lock, less the minimum balance if
+	/// `keep_alive` is set.
+	///
+	/// The locked amount is the largest lock reported by `Pallet::locks`, so
+	/// runtimes must configure `Config::NativeLocks` and
+	/// `Config::CurrencyLocks` for locks to be left behind.
+	pub fn transferable_balance(currency_id: CurrencyIdOf<T>, who: &T::AccountId, keep_alive: bool) -> BalanceOf<T> {
+		let free = <Self as Stp258Currency<T::AccountId>>::free_balance(currency_id, who);
+		let frozen = Self::locks(currency_id, who)
+			.into_iter()
+			.map(|(_, amount)| amount)
+			.max()
+			.unwrap_or_else(Zero::zero);
+		let transferable = free.saturating_sub(frozen);
+
+		if keep_alive {
+			let minimum_balance = <Self as Stp258Currency<T::AccountId>>::minimum_balance(currency_id);
+			transferable.min(free.saturating_sub(minimum_balance))
+		} else {
+			transferable
 		}
 	}
 }
@@ -162,6 +1812,8 @@ impl<T: Config> Stp258Currency<T::AccountId> for Pallet<T> {
 	fn base_unit(currency_id: Self::CurrencyId) -> Self::Balance {
 		if currency_id == T::GetStp258NativeId::get() {
 			T::Stp258Native::minimum_balance()
+		} else if let Some(metadata) = Self::currency_metadata(currency_id) {
+			Self::Balance::from(10u32).saturating_pow(metadata.decimals as usize)
 		} else {
 			T::Stp258Currency::base_unit(currency_id)
 		}
@@ -213,19 +1865,11 @@ impl<T: Config> Stp258Currency<T::AccountId> for Pallet<T> {
 		to: &T::AccountId,
 		amount: Self::Balance,
 	) -> DispatchResult {
-		if amount.is_zero() || from == to {
-			return Ok(());
-		}
-		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::transfer(from, to, amount)?;
-		} else {
-			T::Stp258Currency::transfer(currency_id, from, to, amount)?;
-		}
-		Self::deposit_event(Event::Transferred(currency_id, from.clone(), to.clone(), amount));
-		Ok(())
+		Self::do_transfer(currency_id, from, to, amount, ExistenceRequirement::AllowDeath)
 	}
 
 	fn deposit(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		Self::ensure_not_paused(currency_id)?;
 		if amount.is_zero() {
 			return Ok(());
 		}
@@ -239,16 +1883,13 @@ impl<T: Config> Stp258Currency<T::AccountId> for Pallet<T> {
 	}
 
 	fn withdraw(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
-		if amount.is_zero() {
-			return Ok(());
-		}
-		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::withdraw(who, amount)?;
-		} else {
-			T::Stp258Currency::withdraw(currency_id, who, amount)?;
-		}
-		Self::deposit_event(Event::Withdrawn(currency_id, who.clone(), amount));
-		Ok(())
+		Self::withdraw_with_reasons(
+			currency_id,
+			who,
+			amount,
+			WithdrawReasons::all(),
+			ExistenceRequirement::AllowDeath,
+		)
 	}
 
 	fn can_slash(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> bool {
@@ -291,32 +1932,170 @@ impl<T: Config> Stp258CurrencyLockable<T::AccountId> for Pallet<T> {
 		who: &T::AccountId,
 		amount: Self::Balance,
 	) -> DispatchResult {
+		Self::set_lock_with_reasons(lock_id, currency_id, who, amount, WithdrawReasons::all())
+	}
+
+	fn extend_lock(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		Self::extend_lock_with_reasons(lock_id, currency_id, who, amount, WithdrawReasons::all())
+	}
+
+	fn remove_lock(lock_id: LockIdentifier, currency_id: Self::CurrencyId, who: &T::AccountId) -> DispatchResult {
 		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::set_lock(lock_id, who, amount)
+			T::Stp258Native::remove_lock(lock_id, who)?;
 		} else {
-			T::Stp258Currency::set_lock(lock_id, currency_id, who, amount)
+			T::Stp258Currency::remove_lock(lock_id, currency_id, who)?;
 		}
+		Self::deposit_event(Event::LockRemoved(lock_id, currency_id, who.clone()));
+		Ok(())
 	}
+}
 
-	fn extend_lock(
+impl<T: Config> Stp258CurrencyNamedReservable<T::AccountId> for Pallet<T> {
+	fn reserved_balance_named(
+		id: &ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+	) -> Self::Balance {
+		NamedReserves::<T>::get(who, (currency_id, *id))
+	}
+
+	fn reserve_named(
+		id: &ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> DispatchResult {
+		if value.is_zero() {
+			return Ok(());
+		}
+
+		<Self as Stp258CurrencyReservable<T::AccountId>>::reserve(currency_id, who, value)?;
+		let reserved = Self::reserved_balance_named(id, currency_id, who);
+		Self::set_named_reserve(id, currency_id, who, reserved.saturating_add(value));
+		Ok(())
+	}
+
+	fn unreserve_named(
+		id: &ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> Self::Balance {
+		let reserved = Self::reserved_balance_named(id, currency_id, who);
+		let to_unreserve = value.min(reserved);
+		let remaining = <Self as Stp258CurrencyReservable<T::AccountId>>::unreserve(currency_id, who, to_unreserve);
+		let unreserved = to_unreserve - remaining;
+		Self::set_named_reserve(id, currency_id, who, reserved - unreserved);
+		value - unreserved
+	}
+
+	fn slash_reserved_named(
+		id: &ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> Self::Balance {
+		let reserved = Self::reserved_balance_named(id, currency_id, who);
+		let to_slash = value.min(reserved);
+		let remaining = <Self as Stp258CurrencyReservable<T::AccountId>>::slash_reserved(currency_id, who, to_slash);
+		let slashed = to_slash - remaining;
+		Self::set_named_reserve(id, currency_id, who, reserved - slashed);
+		value - slashed
+	}
+
+	fn repatriate_reserved_named(
+		id: &ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		value: Self::Balance,
+		status: BalanceStatus,
+	) -> result::Result<Self::Balance, DispatchError> {
+		let reserved = Self::reserved_balance_named(id, currency_id, slashed);
+		let to_move = value.min(reserved);
+		let remaining = <Self as Stp258CurrencyReservable<T::AccountId>>::repatriate_reserved(
+			currency_id,
+			slashed,
+			beneficiary,
+			to_move,
+			status,
+		)?;
+		let moved = to_move - remaining;
+		Self::set_named_reserve(id, currency_id, slashed, reserved - moved);
+		if status == BalanceStatus::Reserved {
+			let beneficiary_reserved = Self::reserved_balance_named(id, currency_id, beneficiary);
+			Self::set_named_reserve(id, currency_id, beneficiary, beneficiary_reserved.saturating_add(moved));
+		}
+		Ok(value - moved)
+	}
+}
+
+impl<T: Config> Stp258CurrencyWithdrawReasons<T::AccountId> for Pallet<T> {
+	fn set_lock_with_reasons(
 		lock_id: LockIdentifier,
 		currency_id: Self::CurrencyId,
 		who: &T::AccountId,
 		amount: Self::Balance,
+		reasons: WithdrawReasons,
 	) -> DispatchResult {
 		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::extend_lock(lock_id, who, amount)
+			T::Stp258Native::set_lock_with_reasons(lock_id, who, amount, reasons)?;
 		} else {
-			T::Stp258Currency::extend_lock(lock_id, currency_id, who, amount)
+			T::Stp258Currency::set_lock(lock_id, currency_id, who, amount)?;
 		}
+		Self::deposit_event(Event::LockSet(lock_id, currency_id, who.clone(), amount));
+		Ok(())
 	}
 
-	fn remove_lock(lock_id: LockIdentifier, currency_id: Self::CurrencyId, who: &T::AccountId) -> DispatchResult {
+	fn extend_lock_with_reasons(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		if currency_id == T::GetStp258NativeId::get() {
+			T::Stp258Native::extend_lock_with_reasons(lock_id, who, amount, reasons)?;
+		} else {
+			T::Stp258Currency::extend_lock(lock_id, currency_id, who, amount)?;
+		}
+		Self::deposit_event(Event::LockExtended(lock_id, currency_id, who.clone(), amount));
+		Ok(())
+	}
+
+	fn ensure_can_withdraw_with_reasons(
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
 		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::remove_lock(lock_id, who)
+			T::Stp258Native::ensure_can_withdraw_with_reasons(who, amount, reasons)
 		} else {
-			T::Stp258Currency::remove_lock(lock_id, currency_id, who)
+			T::Stp258Currency::ensure_can_withdraw(currency_id, who, amount)
+		}
+	}
+
+	fn withdraw_with_reasons(
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		Self::ensure_not_paused(currency_id)?;
+		Self::ensure_not_frozen(currency_id, who)?;
+		if amount.is_zero() {
+			return Ok(());
 		}
+		Self::withdraw_unchecked(currency_id, who, amount, reasons, existence_requirement)?;
+		Self::deposit_event(Event::Withdrawn(currency_id, who.clone(), amount));
+		Ok(())
 	}
 }
 
@@ -330,50 +2109,282 @@ impl<T: Config> Stp258CurrencyReservable<T::AccountId> for Pallet<T> {
 	}
 
 	fn slash_reserved(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
-		if currency_id == T::GetStp258NativeId::get() {
+		let remaining = if currency_id == T::GetStp258NativeId::get() {
 			T::Stp258Native::slash_reserved(who, value)
 		} else {
 			T::Stp258Currency::slash_reserved(currency_id, who, value)
+		};
+		let slashed = value.saturating_sub(remaining);
+		if !slashed.is_zero() {
+			Self::deposit_event(Event::ReserveSlashed(currency_id, who.clone(), slashed));
+		}
+		remaining
+	}
+
+	fn reserved_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
+		if currency_id == T::GetStp258NativeId::get() {
+			T::Stp258Native::reserved_balance(who)
+		} else {
+			T::Stp258Currency::reserved_balance(currency_id, who)
+		}
+	}
+
+	fn reserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> DispatchResult {
+		Self::ensure_not_frozen(currency_id, who)?;
+		if currency_id == T::GetStp258NativeId::get() {
+			T::Stp258Native::reserve(who, value)?;
+		} else {
+			T::Stp258Currency::reserve(currency_id, who, value)?;
+		}
+		if !value.is_zero() {
+			Self::deposit_event(Event::Reserved(currency_id, who.clone(), value));
+		}
+		Ok(())
+	}
+
+	fn unreserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
+		let remaining = if currency_id == T::GetStp258NativeId::get() {
+			T::Stp258Native::unreserve(who, value)
+		} else {
+			T::Stp258Currency::unreserve(currency_id, who, value)
+		};
+		let unreserved = value.saturating_sub(remaining);
+		if !unreserved.is_zero() {
+			Self::deposit_event(Event::Unreserved(currency_id, who.clone(), unreserved));
 		}
+		remaining
 	}
 
-	fn reserved_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
-		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::reserved_balance(who)
-		} else {
-			T::Stp258Currency::reserved_balance(currency_id, who)
-		}
-	}
+	fn repatriate_reserved(
+		currency_id: Self::CurrencyId,
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		value: Self::Balance,
+		status: BalanceStatus,
+	) -> result::Result<Self::Balance, DispatchError> {
+		Self::ensure_not_frozen(currency_id, slashed)?;
+		let remaining = if currency_id == T::GetStp258NativeId::get() {
+			T::Stp258Native::repatriate_reserved(slashed, beneficiary, value, status)?
+		} else {
+			T::Stp258Currency::repatriate_reserved(currency_id, slashed, beneficiary, value, status)?
+		};
+		let repatriated = value.saturating_sub(remaining);
+		if !repatriated.is_zero() {
+			Self::deposit_event(Event::ReserveRepatriated(
+				currency_id,
+				slashed.clone(),
+				beneficiary.clone(),
+				repatriated,
+				status,
+			));
+		}
+		Ok(remaining)
+	}
+}
+
+/// Extended `Stp258Asset` with transfers honouring an
+/// `ExistenceRequirement`.
+pub trait Stp258AssetExistential<AccountId>: Stp258Asset<AccountId> {
+	/// Transfer some amount from one account to another, reaping `from` only
+	/// if `existence_requirement` allows it.
+	fn transfer_with_existence(
+		from: &AccountId,
+		to: &AccountId,
+		amount: Self::Balance,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult;
+}
+
+/// Extended `Stp258AssetLockable` with locks and withdrawals for given
+/// `WithdrawReasons`, so that e.g. a governance lock need not block fee
+/// payment.
+pub trait Stp258AssetWithdrawReasons<AccountId>: Stp258AssetLockable<AccountId> {
+	/// Create or amend a lock on the balance of `who`, restricting only
+	/// withdrawals for `reasons`.
+	fn set_lock_with_reasons(
+		lock_id: LockIdentifier,
+		who: &AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult;
+
+	/// Extend a lock on the balance of `who`, restricting only withdrawals
+	/// for `reasons`.
+	fn extend_lock_with_reasons(
+		lock_id: LockIdentifier,
+		who: &AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult;
+
+	/// Ensure `amount` can be withdrawn from `who` for `reasons`.
+	fn ensure_can_withdraw_with_reasons(
+		who: &AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult;
+
+	/// Withdraw `amount` from `who` for `reasons`, reaping `who` only if
+	/// `existence_requirement` allows it.
+	fn withdraw_with_reasons(
+		who: &AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult;
+}
+
+/// Extended `Stp258CurrencyLockable` with locks and withdrawals for given
+/// `WithdrawReasons`.
+///
+/// Only the native currency distinguishes reasons, locks on other currencies
+/// restrict every withdrawal.
+pub trait Stp258CurrencyWithdrawReasons<AccountId>: Stp258CurrencyLockable<AccountId> {
+	/// Create or amend a lock on the balance of `who` in `currency_id`,
+	/// restricting only withdrawals for `reasons`.
+	fn set_lock_with_reasons(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult;
+
+	/// Extend a lock on the balance of `who` in `currency_id`, restricting
+	/// only withdrawals for `reasons`.
+	fn extend_lock_with_reasons(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult;
+
+	/// Ensure `amount` of `currency_id` can be withdrawn from `who` for
+	/// `reasons`.
+	fn ensure_can_withdraw_with_reasons(
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult;
+
+	/// Withdraw `amount` of `currency_id` from `who` for `reasons`, reaping
+	/// `who` only if `existence_requirement` allows it.
+	fn withdraw_with_reasons(
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult;
+}
+
+/// Read the locks on the balance of an account in the underlying native
+/// currency, e.g. `pallet-balances`, which `Stp258AssetLockable` does not
+/// expose.
+pub trait Stp258AssetLocksInspect<AccountId, Balance> {
+	/// The `(lock_id, amount)` locks on the balance of `who`.
+	fn locks(who: &AccountId) -> Vec<(LockIdentifier, Balance)>;
+}
+
+impl<AccountId, Balance> Stp258AssetLocksInspect<AccountId, Balance> for () {
+	fn locks(_who: &AccountId) -> Vec<(LockIdentifier, Balance)> {
+		Vec::new()
+	}
+}
+
+/// Read the locks on the balance of an account in the underlying
+/// multi-currency, e.g. `stp258-serp`, which `Stp258CurrencyLockable` does
+/// not expose.
+pub trait Stp258CurrencyLocksInspect<AccountId, CurrencyId, Balance> {
+	/// The `(lock_id, amount)` locks on the balance of `who` in
+	/// `currency_id`.
+	fn locks(currency_id: CurrencyId, who: &AccountId) -> Vec<(LockIdentifier, Balance)>;
+}
+
+impl<AccountId, CurrencyId, Balance> Stp258CurrencyLocksInspect<AccountId, CurrencyId, Balance> for () {
+	fn locks(_currency_id: CurrencyId, _who: &AccountId) -> Vec<(LockIdentifier, Balance)> {
+		Vec::new()
+	}
+}
+
+/// Extended `Stp258CurrencyReservable` with reserves named by a
+/// `ReserveIdentifier`, so that several modules can reserve balance of the
+/// same account without colliding.
+///
+/// A named reserve is part of the anonymous reserve of the account.
+pub trait Stp258CurrencyNamedReservable<AccountId>: Stp258CurrencyReservable<AccountId> {
+	/// The reserved balance of `who` in `currency_id` under `id`.
+	fn reserved_balance_named(id: &ReserveIdentifier, currency_id: Self::CurrencyId, who: &AccountId) -> Self::Balance;
+
+	/// Move `value` of `currency_id` from the free balance of `who` to its
+	/// reserve under `id`.
+	fn reserve_named(
+		id: &ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		value: Self::Balance,
+	) -> DispatchResult;
+
+	/// Move up to `value` of `currency_id` from the reserve of `who` under
+	/// `id` to its free balance, returning the amount which could not be
+	/// unreserved.
+	fn unreserve_named(
+		id: &ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		value: Self::Balance,
+	) -> Self::Balance;
+
+	/// Slash up to `value` of `currency_id` from the reserve of `who` under
+	/// `id`, returning the amount which could not be slashed.
+	fn slash_reserved_named(
+		id: &ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &AccountId,
+		value: Self::Balance,
+	) -> Self::Balance;
+
+	/// Move up to `value` of `currency_id` from the reserve of `slashed`
+	/// under `id` to `beneficiary`, in its reserve under `id` if `status` is
+	/// `Reserved`, returning the amount which could not be moved.
+	fn repatriate_reserved_named(
+		id: &ReserveIdentifier,
+		currency_id: Self::CurrencyId,
+		slashed: &AccountId,
+		beneficiary: &AccountId,
+		value: Self::Balance,
+		status: BalanceStatus,
+	) -> result::Result<Self::Balance, DispatchError>;
+}
+
+/// Extended `Stp258AssetReservable` with reserves named by a
+/// `ReserveIdentifier`.
+pub trait Stp258AssetNamedReservable<AccountId>: Stp258AssetReservable<AccountId> {
+	/// The reserved balance of `who` under `id`.
+	fn reserved_balance_named(id: &ReserveIdentifier, who: &AccountId) -> Self::Balance;
 
-	fn reserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> DispatchResult {
-		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::reserve(who, value)
-		} else {
-			T::Stp258Currency::reserve(currency_id, who, value)
-		}
-	}
+	/// Move `value` from the free balance of `who` to its reserve under `id`.
+	fn reserve_named(id: &ReserveIdentifier, who: &AccountId, value: Self::Balance) -> DispatchResult;
 
-	fn unreserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
-		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::unreserve(who, value)
-		} else {
-			T::Stp258Currency::unreserve(currency_id, who, value)
-		}
-	}
+	/// Move up to `value` from the reserve of `who` under `id` to its free
+	/// balance, returning the amount which could not be unreserved.
+	fn unreserve_named(id: &ReserveIdentifier, who: &AccountId, value: Self::Balance) -> Self::Balance;
 
-	fn repatriate_reserved(
-		currency_id: Self::CurrencyId,
-		slashed: &T::AccountId,
-		beneficiary: &T::AccountId,
+	/// Slash up to `value` from the reserve of `who` under `id`, returning
+	/// the amount which could not be slashed.
+	fn slash_reserved_named(id: &ReserveIdentifier, who: &AccountId, value: Self::Balance) -> Self::Balance;
+
+	/// Move up to `value` from the reserve of `slashed` under `id` to
+	/// `beneficiary`, returning the amount which could not be moved.
+	fn repatriate_reserved_named(
+		id: &ReserveIdentifier,
+		slashed: &AccountId,
+		beneficiary: &AccountId,
 		value: Self::Balance,
 		status: BalanceStatus,
-	) -> result::Result<Self::Balance, DispatchError> {
-		if currency_id == T::GetStp258NativeId::get() {
-			T::Stp258Native::repatriate_reserved(slashed, beneficiary, value, status)
-		} else {
-			T::Stp258Currency::repatriate_reserved(currency_id, slashed, beneficiary, value, status)
-		}
-	}
+	) -> result::Result<Self::Balance, DispatchError>;
 }
 
 pub struct Currency<T, GetCurrencyId>(marker::PhantomData<T>, marker::PhantomData<GetCurrencyId>);
@@ -426,6 +2437,21 @@ where
 	}
 }
 
+impl<T, GetCurrencyId> Stp258AssetExistential<T::AccountId> for Currency<T, GetCurrencyId>
+where
+	T: Config,
+	GetCurrencyId: Get<CurrencyIdOf<T>>,
+{
+	fn transfer_with_existence(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: Self::Balance,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		<Pallet<T>>::do_transfer(GetCurrencyId::get(), from, to, amount, existence_requirement)
+	}
+}
+
 impl<T, GetCurrencyId> Stp258AssetExtended<T::AccountId> for Currency<T, GetCurrencyId>
 where
 	T: Config,
@@ -458,6 +2484,47 @@ where
 	}
 }
 
+impl<T, GetCurrencyId> Stp258AssetWithdrawReasons<T::AccountId> for Currency<T, GetCurrencyId>
+where
+	T: Config,
+	GetCurrencyId: Get<CurrencyIdOf<T>>,
+{
+	fn set_lock_with_reasons(
+		lock_id: LockIdentifier,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		<Pallet<T>>::set_lock_with_reasons(lock_id, GetCurrencyId::get(), who, amount, reasons)
+	}
+
+	fn extend_lock_with_reasons(
+		lock_id: LockIdentifier,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		<Pallet<T>>::extend_lock_with_reasons(lock_id, GetCurrencyId::get(), who, amount, reasons)
+	}
+
+	fn ensure_can_withdraw_with_reasons(
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		<Pallet<T>>::ensure_can_withdraw_with_reasons(GetCurrencyId::get(), who, amount, reasons)
+	}
+
+	fn withdraw_with_reasons(
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		<Pallet<T>>::withdraw_with_reasons(GetCurrencyId::get(), who, amount, reasons, existence_requirement)
+	}
+}
+
 impl<T, GetCurrencyId> Stp258AssetReservable<T::AccountId> for Currency<T, GetCurrencyId>
 where
 	T: Config,
@@ -499,19 +2566,61 @@ where
 	}
 }
 
+impl<T, GetCurrencyId> Stp258AssetNamedReservable<T::AccountId> for Currency<T, GetCurrencyId>
+where
+	T: Config,
+	GetCurrencyId: Get<CurrencyIdOf<T>>,
+{
+	fn reserved_balance_named(id: &ReserveIdentifier, who: &T::AccountId) -> Self::Balance {
+		<Pallet<T>>::reserved_balance_named(id, GetCurrencyId::get(), who)
+	}
+
+	fn reserve_named(id: &ReserveIdentifier, who: &T::AccountId, value: Self::Balance) -> DispatchResult {
+		<Pallet<T>>::reserve_named(id, GetCurrencyId::get(), who, value)
+	}
+
+	fn unreserve_named(id: &ReserveIdentifier, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
+		<Pallet<T>>::unreserve_named(id, GetCurrencyId::get(), who, value)
+	}
+
+	fn slash_reserved_named(id: &ReserveIdentifier, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
+		<Pallet<T>>::slash_reserved_named(id, GetCurrencyId::get(), who, value)
+	}
+
+	fn repatriate_reserved_named(
+		id: &ReserveIdentifier,
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		value: Self::Balance,
+		status: BalanceStatus,
+	) -> result::Result<Self::Balance, DispatchError> {
+		<Pallet<T>>::repatriate_reserved_named(id, GetCurrencyId::get(), slashed, beneficiary, value, status)
+	}
+}
+
 pub type Stp258NativeOf<T> = Currency<T, <T as Config>::GetStp258NativeId>;
 
 /// Adapt other currency traits implementation to `Stp258Asset`.
-pub struct Stp258AssetAdapter<T, Currency, Amount, Moment>(marker::PhantomData<(T, Currency, Amount, Moment)>);
+///
+/// Imbalances created by `deposit` are handed to `OnDeposit`, and those
+/// created by `slash` and `slash_reserved` to `OnSlash`. The `()` defaults
+/// drop them.
+pub struct Stp258AssetAdapter<T, Currency, Amount, Moment, OnSlash = (), OnDeposit = ()>(
+	marker::PhantomData<(T, Currency, Amount, Moment, OnSlash, OnDeposit)>,
+);
 
 type PalletBalanceOf<A, Currency> = <Currency as SetheumCurrency<A>>::Balance;
+type PalletPositiveImbalanceOf<A, Currency> = <Currency as SetheumCurrency<A>>::PositiveImbalance;
+type PalletNegativeImbalanceOf<A, Currency> = <Currency as SetheumCurrency<A>>::NegativeImbalance;
 
 // Adapt `frame_support::traits::Currency`
-impl<T, AccountId, Currency, Amount, Moment> Stp258Asset<AccountId>
-	for Stp258AssetAdapter<T, Currency, Amount, Moment>
+impl<T, AccountId, Currency, Amount, Moment, OnSlash, OnDeposit> Stp258Asset<AccountId>
+	for Stp258AssetAdapter<T, Currency, Amount, Moment, OnSlash, OnDeposit>
 where
 	Currency: SetheumCurrency<AccountId>,
 	T: Config,
+	OnSlash: OnUnbalanced<PalletNegativeImbalanceOf<AccountId, Currency>>,
+	OnDeposit: OnUnbalanced<PalletPositiveImbalanceOf<AccountId, Currency>>,
 {
 	type Balance = PalletBalanceOf<AccountId, Currency>;
 
@@ -544,7 +2653,7 @@ where
 	}
 
 	fn deposit(who: &AccountId, amount: Self::Balance) -> DispatchResult {
-		let _ = Currency::deposit_creating(who, amount);
+		OnDeposit::on_unbalanced(Currency::deposit_creating(who, amount));
 		Ok(())
 	}
 
@@ -557,14 +2666,34 @@ where
 	}
 
 	fn slash(who: &AccountId, amount: Self::Balance) -> Self::Balance {
-		let (_, gap) = Currency::slash(who, amount);
+		let (imbalance, gap) = Currency::slash(who, amount);
+		OnSlash::on_unbalanced(imbalance);
 		gap
 	}
 }
 
 // Adapt `frame_support::traits::Currency`
-impl<T, AccountId, Currency, Amount, Moment> Stp258AssetExtended<AccountId>
-	for Stp258AssetAdapter<T, Currency, Amount, Moment>
+impl<T, AccountId, Currency, Amount, Moment, OnSlash, OnDeposit> Stp258AssetExistential<AccountId>
+	for Stp258AssetAdapter<T, Currency, Amount, Moment, OnSlash, OnDeposit>
+where
+	Currency: SetheumCurrency<AccountId>,
+	T: Config,
+	OnSlash: OnUnbalanced<PalletNegativeImbalanceOf<AccountId, Currency>>,
+	OnDeposit: OnUnbalanced<PalletPositiveImbalanceOf<AccountId, Currency>>,
+{
+	fn transfer_with_existence(
+		from: &AccountId,
+		to: &AccountId,
+		amount: Self::Balance,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		Currency::transfer(from, to, amount, existence_requirement)
+	}
+}
+
+// Adapt `frame_support::traits::Currency`
+impl<T, AccountId, Currency, Amount, Moment, OnSlash, OnDeposit> Stp258AssetExtended<AccountId>
+	for Stp258AssetAdapter<T, Currency, Amount, Moment, OnSlash, OnDeposit>
 where
 	Amount: Signed
 		+ TryInto<PalletBalanceOf<AccountId, Currency>>
@@ -577,6 +2706,8 @@ where
 		+ Default,
 	Currency: SetheumCurrency<AccountId>,
 	T: Config,
+	OnSlash: OnUnbalanced<PalletNegativeImbalanceOf<AccountId, Currency>>,
+	OnDeposit: OnUnbalanced<PalletPositiveImbalanceOf<AccountId, Currency>>,
 {
 	type Amount = Amount;
 
@@ -594,11 +2725,13 @@ where
 }
 
 // Adapt `frame_support::traits::LockableCurrency`
-impl<T, AccountId, Currency, Amount, Moment> Stp258AssetLockable<AccountId>
-	for Stp258AssetAdapter<T, Currency, Amount, Moment>
+impl<T, AccountId, Currency, Amount, Moment, OnSlash, OnDeposit> Stp258AssetLockable<AccountId>
+	for Stp258AssetAdapter<T, Currency, Amount, Moment, OnSlash, OnDeposit>
 where
 	Currency: SetheumLockableCurrency<AccountId>,
 	T: Config,
+	OnSlash: OnUnbalanced<PalletNegativeImbalanceOf<AccountId, Currency>>,
+	OnDeposit: OnUnbalanced<PalletPositiveImbalanceOf<AccountId, Currency>>,
 {
 	type Moment = Moment;
 
@@ -618,19 +2751,73 @@ where
 	}
 }
 
+// Adapt `frame_support::traits::LockableCurrency`
+impl<T, AccountId, Currency, Amount, Moment, OnSlash, OnDeposit> Stp258AssetWithdrawReasons<AccountId>
+	for Stp258AssetAdapter<T, Currency, Amount, Moment, OnSlash, OnDeposit>
+where
+	Currency: SetheumLockableCurrency<AccountId>,
+	T: Config,
+	OnSlash: OnUnbalanced<PalletNegativeImbalanceOf<AccountId, Currency>>,
+	OnDeposit: OnUnbalanced<PalletPositiveImbalanceOf<AccountId, Currency>>,
+{
+	fn set_lock_with_reasons(
+		lock_id: LockIdentifier,
+		who: &AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Currency::set_lock(lock_id, who, amount, reasons);
+		Ok(())
+	}
+
+	fn extend_lock_with_reasons(
+		lock_id: LockIdentifier,
+		who: &AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		Currency::extend_lock(lock_id, who, amount, reasons);
+		Ok(())
+	}
+
+	fn ensure_can_withdraw_with_reasons(
+		who: &AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		let new_balance = Self::free_balance(who)
+			.checked_sub(&amount)
+			.ok_or(Error::<T>::BalanceTooLow)?;
+
+		Currency::ensure_can_withdraw(who, amount, reasons, new_balance)
+	}
+
+	fn withdraw_with_reasons(
+		who: &AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		Currency::withdraw(who, amount, reasons, existence_requirement).map(|_| ())
+	}
+}
+
 // Adapt `frame_support::traits::ReservableCurrency`
-impl<T, AccountId, Currency, Amount, Moment> Stp258AssetReservable<AccountId>
-	for Stp258AssetAdapter<T, Currency, Amount, Moment>
+impl<T, AccountId, Currency, Amount, Moment, OnSlash, OnDeposit> Stp258AssetReservable<AccountId>
+	for Stp258AssetAdapter<T, Currency, Amount, Moment, OnSlash, OnDeposit>
 where
 	Currency: SetheumReservableCurrency<AccountId>,
 	T: Config,
+	OnSlash: OnUnbalanced<PalletNegativeImbalanceOf<AccountId, Currency>>,
+	OnDeposit: OnUnbalanced<PalletPositiveImbalanceOf<AccountId, Currency>>,
 {
 	fn can_reserve(who: &AccountId, value: Self::Balance) -> bool {
 		Currency::can_reserve(who, value)
 	}
 
 	fn slash_reserved(who: &AccountId, value: Self::Balance) -> Self::Balance {
-		let (_, gap) = Currency::slash_reserved(who, value);
+		let (imbalance, gap) = Currency::slash_reserved(who, value);
+		OnSlash::on_unbalanced(imbalance);
 		gap
 	}
 
@@ -656,8 +2843,239 @@ where
 	}
 }
 
+/// Adapt a currency of this module to the `frame_support` currency traits.
+///
+/// As with `pallet-balances`, the total issuance it reports changes when
+/// `burn` and `issue` are called and when imbalances are dropped, not when
+/// balances are deposited or withdrawn. The underlying currencies update their
+/// total issuance together with the balance, so the difference is kept in
+/// `PendingIssuance` while imbalances are alive.
+///
+/// Transfers through the adapter are made for the calling pallet rather than
+/// the account holder, so they are not charged transfer fees and are not
+/// blocked by paused currencies or frozen accounts.
+pub struct SetheumCurrencyAdapter<T, GetCurrencyId>(marker::PhantomData<(T, GetCurrencyId)>);
+
+impl<T, GetCurrencyId> SetheumCurrency<T::AccountId> for SetheumCurrencyAdapter<T, GetCurrencyId>
+where
+	T: Config,
+	GetCurrencyId: Get<CurrencyIdOf<T>>,
+{
+	type Balance = BalanceOf<T>;
+	type PositiveImbalance = PositiveImbalance<T, GetCurrencyId>;
+	type NegativeImbalance = NegativeImbalance<T, GetCurrencyId>;
+
+	fn total_balance(who: &T::AccountId) -> Self::Balance {
+		<Pallet<T>>::total_balance(GetCurrencyId::get(), who)
+	}
+
+	fn can_slash(who: &T::AccountId, value: Self::Balance) -> bool {
+		<Pallet<T>>::can_slash(GetCurrencyId::get(), who, value)
+	}
+
+	fn total_issuance() -> Self::Balance {
+		<Pallet<T>>::adapted_total_issuance(GetCurrencyId::get())
+	}
+
+	fn minimum_balance() -> Self::Balance {
+		<Pallet<T>>::minimum_balance(GetCurrencyId::get())
+	}
+
+	fn burn(amount: Self::Balance) -> Self::PositiveImbalance {
+		let amount = amount.min(Self::total_issuance());
+		<Pallet<T>>::lower_pending_issuance(GetCurrencyId::get(), amount);
+		PositiveImbalance::new(amount)
+	}
+
+	fn issue(amount: Self::Balance) -> Self::NegativeImbalance {
+		let amount = amount.min(Self::Balance::max_value() - Self::total_issuance());
+		<Pallet<T>>::raise_pending_issuance(GetCurrencyId::get(), amount);
+		NegativeImbalance::new(amount)
+	}
+
+	fn free_balance(who: &T::AccountId) -> Self::Balance {
+		<Pallet<T>>::free_balance(GetCurrencyId::get(), who)
+	}
+
+	fn ensure_can_withdraw(
+		who: &T::AccountId,
+		amount: Self::Balance,
+		reasons: WithdrawReasons,
+		_new_balance: Self::Balance,
+	) -> DispatchResult {
+		<Pallet<T>>::ensure_can_withdraw_with_reasons(GetCurrencyId::get(), who, amount, reasons)
+	}
+
+	fn transfer(
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		value: Self::Balance,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		if value.is_zero() || source == dest {
+			return Ok(());
+		}
+		let currency_id = GetCurrencyId::get();
+		<Pallet<T>>::transfer_unchecked(currency_id, source, dest, value, existence_requirement)?;
+		<Pallet<T>>::deposit_event(Event::Transferred(currency_id, source.clone(), dest.clone(), value));
+		Ok(())
+	}
+
+	fn slash(who: &T::AccountId, value: Self::Balance) -> (Self::NegativeImbalance, Self::Balance) {
+		if value.is_zero() {
+			return (Self::NegativeImbalance::zero(), value);
+		}
+
+		let remaining = <Pallet<T>>::slash(GetCurrencyId::get(), who, value);
+		<Pallet<T>>::raise_pending_issuance(GetCurrencyId::get(), value - remaining);
+		(Self::NegativeImbalance::new(value - remaining), remaining)
+	}
+
+	fn deposit_into_existing(
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> result::Result<Self::PositiveImbalance, DispatchError> {
+		if value.is_zero() {
+			return Ok(Self::PositiveImbalance::zero());
+		}
+		ensure!(!Self::total_balance(who).is_zero(), Error::<T>::DeadAccount);
+
+		<Pallet<T>>::deposit(GetCurrencyId::get(), who, value)?;
+		<Pallet<T>>::lower_pending_issuance(GetCurrencyId::get(), value);
+		Ok(Self::PositiveImbalance::new(value))
+	}
+
+	fn deposit_creating(who: &T::AccountId, value: Self::Balance) -> Self::PositiveImbalance {
+		if value.is_zero() {
+			return Self::PositiveImbalance::zero();
+		}
+
+		<Pallet<T>>::deposit(GetCurrencyId::get(), who, value).map_or_else(
+			|_| Self::PositiveImbalance::zero(),
+			|_| {
+				<Pallet<T>>::lower_pending_issuance(GetCurrencyId::get(), value);
+				Self::PositiveImbalance::new(value)
+			},
+		)
+	}
+
+	fn withdraw(
+		who: &T::AccountId,
+		value: Self::Balance,
+		reasons: WithdrawReasons,
+		liveness: ExistenceRequirement,
+	) -> result::Result<Self::NegativeImbalance, DispatchError> {
+		if value.is_zero() {
+			return Ok(Self::NegativeImbalance::zero());
+		}
+
+		<Pallet<T>>::withdraw_with_reasons(GetCurrencyId::get(), who, value, reasons, liveness)?;
+		<Pallet<T>>::raise_pending_issuance(GetCurrencyId::get(), value);
+		Ok(Self::NegativeImbalance::new(value))
+	}
+
+	fn make_free_balance_be(
+		who: &T::AccountId,
+		value: Self::Balance,
+	) -> SignedImbalance<Self::Balance, Self::PositiveImbalance> {
+		let free = Self::free_balance(who);
+		if value >= free {
+			SignedImbalance::Positive(Self::deposit_creating(who, value - free))
+		} else {
+			SignedImbalance::Negative(
+				Self::withdraw(who, free - value, WithdrawReasons::all(), ExistenceRequirement::AllowDeath)
+					.unwrap_or_else(|_| Self::NegativeImbalance::zero()),
+			)
+		}
+	}
+}
+
+impl<T, GetCurrencyId> SetheumReservableCurrency<T::AccountId> for SetheumCurrencyAdapter<T, GetCurrencyId>
+where
+	T: Config,
+	GetCurrencyId: Get<CurrencyIdOf<T>>,
+{
+	fn can_reserve(who: &T::AccountId, value: Self::Balance) -> bool {
+		<Pallet<T> as Stp258CurrencyReservable<T::AccountId>>::can_reserve(GetCurrencyId::get(), who, value)
+	}
+
+	fn slash_reserved(who: &T::AccountId, value: Self::Balance) -> (Self::NegativeImbalance, Self::Balance) {
+		if value.is_zero() {
+			return (Self::NegativeImbalance::zero(), value);
+		}
+
+		let remaining =
+			<Pallet<T> as Stp258CurrencyReservable<T::AccountId>>::slash_reserved(GetCurrencyId::get(), who, value);
+		<Pallet<T>>::raise_pending_issuance(GetCurrencyId::get(), value - remaining);
+		(Self::NegativeImbalance::new(value - remaining), remaining)
+	}
+
+	fn reserved_balance(who: &T::AccountId) -> Self::Balance {
+		<Pallet<T> as Stp258CurrencyReservable<T::AccountId>>::reserved_balance(GetCurrencyId::get(), who)
+	}
+
+	fn reserve(who: &T::AccountId, value: Self::Balance) -> DispatchResult {
+		<Pallet<T> as Stp258CurrencyReservable<T::AccountId>>::reserve(GetCurrencyId::get(), who, value)
+	}
+
+	fn unreserve(who: &T::AccountId, value: Self::Balance) -> Self::Balance {
+		<Pallet<T> as Stp258CurrencyReservable<T::AccountId>>::unreserve(GetCurrencyId::get(), who, value)
+	}
+
+	fn repatriate_reserved(
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		value: Self::Balance,
+		status: BalanceStatus,
+	) -> result::Result<Self::Balance, DispatchError> {
+		<Pallet<T> as Stp258CurrencyReservable<T::AccountId>>::repatriate_reserved(
+			GetCurrencyId::get(),
+			slashed,
+			beneficiary,
+			value,
+			status,
+		)
+	}
+}
+
+impl<T, GetCurrencyId> SetheumLockableCurrency<T::AccountId> for SetheumCurrencyAdapter<T, GetCurrencyId>
+where
+	T: Config,
+	GetCurrencyId: Get<CurrencyIdOf<T>>,
+{
+	type Moment = T::BlockNumber;
+	type MaxLocks = T::MaxLocks;
+
+	fn set_lock(id: LockIdentifier, who: &T::AccountId, amount: Self::Balance, reasons: WithdrawReasons) {
+		if let Err(e) = <Pallet<T>>::set_lock_with_reasons(id, GetCurrencyId::get(), who, amount, reasons) {
+			frame_support::debug::warn!("SetheumCurrencyAdapter failed to set lock {:?}: {:?}", id, e);
+		}
+	}
+
+	fn extend_lock(id: LockIdentifier, who: &T::AccountId, amount: Self::Balance, reasons: WithdrawReasons) {
+		if let Err(e) = <Pallet<T>>::extend_lock_with_reasons(id, GetCurrencyId::get(), who, amount, reasons) {
+			frame_support::debug::warn!("SetheumCurrencyAdapter failed to extend lock {:?}: {:?}", id, e);
+		}
+	}
+
+	fn remove_lock(id: LockIdentifier, who: &T::AccountId) {
+		if let Err(e) = <Pallet<T> as Stp258CurrencyLockable<T::AccountId>>::remove_lock(id, GetCurrencyId::get(), who)
+		{
+			frame_support::debug::warn!("SetheumCurrencyAdapter failed to remove lock {:?}: {:?}", id, e);
+		}
+	}
+}
+
 impl<T: Config> MergeAccount<T::AccountId> for Pallet<T> {
 	fn merge_account(source: &T::AccountId, dest: &T::AccountId) -> DispatchResult {
+		ensure!(
+			FrozenAccounts::<T>::iter_prefix(source).next().is_none(),
+			Error::<T>::AccountFrozen
+		);
+		ensure!(
+			NamedReserves::<T>::iter_prefix(source).next().is_none(),
+			Error::<T>::NamedReservesExist
+		);
 		with_transaction_result(|| {
 			// transfer non-native free to dest
 			T::Stp258Currency::merge_account(source, dest)?;
@@ -3,9 +3,13 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{construct_runtime, parameter_types};
+use frame_support::{
+	construct_runtime, ord_parameter_types, parameter_types, traits::GenesisBuild, weights::IdentityFee,
+};
+use frame_system::{EnsureOneOf, EnsureRoot, EnsureSignedBy};
 use serp_traits::parameter_type_with_key;
 use sp_core::H256;
+use std::cell::RefCell;
 use sp_runtime::{
 	testing::Header,
 	traits::{AccountIdConversion, IdentityLookup},
@@ -64,7 +68,10 @@ impl pallet_balances::Config for Runtime {
 
 parameter_type_with_key! {
 	pub ExistentialDeposits: |currency_id: CurrencyId| -> Balance {
-		Default::default()
+		match currency_id {
+			&JUSD => 100,
+			_ => 0,
+		}
 	};
 }
 
@@ -126,8 +133,38 @@ pub const JUSD: CurrencyId = 3;
 
 pub const ADJUSTMENT_FREQUENCY: Blocknumber = 10;
 
+ord_parameter_types! {
+	pub const SerpCommittee: AccountId = SERPER;
+}
+
 parameter_types! {
 	pub const GetStp258NativeId: CurrencyId = DNAR;
+	pub const GetSettId: CurrencyId = SETT;
+	pub const MaxBatchTransfers: u32 = 4;
+	pub const MaxVestingSchedules: u32 = 3;
+	pub const MaxScheduledPerBlock: u32 = 2;
+	pub const MaxLocks: u32 = 50;
+	pub const SubscriptionDeposit: Balance = 5;
+}
+
+pub struct NativeLocks;
+impl Stp258AssetLocksInspect<AccountId, Balance> for NativeLocks {
+	fn locks(who: &AccountId) -> Vec<(LockIdentifier, Balance)> {
+		PalletBalances::locks(who)
+			.into_iter()
+			.map(|lock| (lock.id, lock.amount))
+			.collect()
+	}
+}
+
+pub struct SerpLocks;
+impl Stp258CurrencyLocksInspect<AccountId, CurrencyId, Balance> for SerpLocks {
+	fn locks(currency_id: CurrencyId, who: &AccountId) -> Vec<(LockIdentifier, Balance)> {
+		Stp258Serp::locks(who, currency_id)
+			.into_iter()
+			.map(|lock| (lock.id, lock.amount))
+			.collect()
+	}
 }
 
 impl Config for Runtime {
@@ -135,10 +172,73 @@ impl Config for Runtime {
 	type Stp258Currency = Stp258Serp;
 	type Stp258Native = AdaptedStp258Asset;
 	type GetStp258NativeId = GetStp258NativeId;
+	type NativeLocks = NativeLocks;
+	type CurrencyLocks = SerpLocks;
+	type UpdateOrigin = EnsureOneOf<AccountId, EnsureRoot<AccountId>, EnsureSignedBy<SerpCommittee, AccountId>>;
+	type PauseOrigin = EnsureRoot<AccountId>;
+	type FreezeOrigin = EnsureRoot<AccountId>;
+	type MaxBatchTransfers = MaxBatchTransfers;
+	type GetSerperAcc = GetSerperAcc;
+	type GetSettPayAcc = GetSettPayAcc;
+	type GetSerperRatio = GetSerperRatio;
+	type MaxVestingSchedules = MaxVestingSchedules;
+	type MaxLocks = MaxLocks;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type SubscriptionDeposit = SubscriptionDeposit;
 	type WeightInfo = ();
 }
+parameter_types! {
+	pub const TransactionByteFee: Balance = 0;
+}
+
+/// Prices one unit of DNAR at 100 units of SETT, fees cannot be paid in
+/// other currencies.
+pub struct MockFeePrice;
+impl FeePriceProvider<CurrencyId, Balance> for MockFeePrice {
+	fn convert_native_fee(currency_id: CurrencyId, fee: Balance) -> Option<Balance> {
+		match currency_id {
+			SETT => fee.checked_mul(100),
+			_ => None,
+		}
+	}
+}
+
+impl pallet_transaction_payment::Config for Runtime {
+	type OnChargeTransaction = Stp258ChargeTransaction<Runtime, MockFeePrice>;
+	type TransactionByteFee = TransactionByteFee;
+	type WeightToFee = IdentityFee<Balance>;
+	type FeeMultiplierUpdate = ();
+}
+
 pub type Stp258Native = Stp258NativeOf<Runtime>;
 pub type AdaptedStp258Asset = Stp258AssetAdapter<Runtime, PalletBalances, i64, u64>;
+pub type SettCurrency = SetheumCurrencyAdapter<Runtime, GetSettId>;
+
+type NativeNegativeImbalance = <PalletBalances as SetheumCurrency<AccountId>>::NegativeImbalance;
+type NativePositiveImbalance = <PalletBalances as SetheumCurrency<AccountId>>::PositiveImbalance;
+
+thread_local! {
+	pub static DEPOSITED: RefCell<Balance> = RefCell::new(0);
+}
+
+/// Sends slashed native funds to the treasury.
+pub struct SlashToTreasury;
+impl OnUnbalanced<NativeNegativeImbalance> for SlashToTreasury {
+	fn on_nonzero_unbalanced(amount: NativeNegativeImbalance) {
+		PalletBalances::resolve_creating(&TREASURY, amount);
+	}
+}
+
+/// Records the native funds minted by deposits.
+pub struct RecordDeposits;
+impl OnUnbalanced<NativePositiveImbalance> for RecordDeposits {
+	fn on_nonzero_unbalanced(amount: NativePositiveImbalance) {
+		DEPOSITED.with(|v| *v.borrow_mut() += amount.peek());
+	}
+}
+
+pub type TreasuryAdaptedStp258Asset =
+	Stp258AssetAdapter<Runtime, PalletBalances, i64, u64, SlashToTreasury, RecordDeposits>;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
 type Block = frame_system::mocking::MockBlock<Runtime>;
@@ -150,9 +250,10 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Module, Call, Storage, Config, Event<T>},
-		Stp258Currencies: stp258_currencies::{Module, Call, Event<T>},
+		Stp258Currencies: stp258_currencies::{Module, Call, Storage, Config<T>, Event<T>},
 		Stp258Serp: stp258_serp::{Module, Storage, Event<T>, Config<T>},
 		PalletBalances: pallet_balances::{Module, Call, Storage, Config<T>, Event<T>},
+		TransactionPayment: pallet_transaction_payment::{Module, Storage},
 	}
 );
 
@@ -161,16 +262,41 @@ pub const ALICE: AccountId = AccountId32::new([0u8; 32]);
 pub const BOB: AccountId = AccountId32::new([1u8; 32]);
 pub const SERPER: AccountId = AccountId32::new([3u8; 32]);
 pub const SETTPAY: AccountId = AccountId32::new([4u8; 32]);
+pub const TREASURY: AccountId = AccountId32::new([5u8; 32]);
 pub const ID_1: LockIdentifier = *b"1       ";
 
+pub fn sett_metadata() -> CurrencyMetadata<Balance> {
+	CurrencyMetadata {
+		name: b"Setheum Dollar".to_vec(),
+		symbol: b"SETT".to_vec(),
+		decimals: 4,
+		is_stablecoin: true,
+		minimum_balance: None,
+	}
+}
+
+pub fn jusd_metadata() -> CurrencyMetadata<Balance> {
+	CurrencyMetadata {
+		name: b"Setheum US Dollar".to_vec(),
+		symbol: b"JUSD".to_vec(),
+		decimals: 3,
+		is_stablecoin: true,
+		minimum_balance: None,
+	}
+}
+
 pub struct ExtBuilder {
 	endowed_accounts: Vec<(AccountId, CurrencyId, Balance)>,
+	currencies: Vec<(CurrencyId, CurrencyMetadata<Balance>)>,
+	vesting: Vec<(AccountId, CurrencyId, Blocknumber, Blocknumber, u32, Balance)>,
 }
 
 impl Default for ExtBuilder {
 	fn default() -> Self {
 		Self {
 			endowed_accounts: vec![],
+			currencies: vec![],
+			vesting: vec![],
 		}
 	}
 }
@@ -181,6 +307,20 @@ impl ExtBuilder {
 		self
 	}
 
+	pub fn currencies(mut self, currencies: Vec<(CurrencyId, CurrencyMetadata<Balance>)>) -> Self {
+		self.currencies = currencies;
+		self
+	}
+
+	pub fn vesting(mut self, vesting: Vec<(AccountId, CurrencyId, Blocknumber, Blocknumber, u32, Balance)>) -> Self {
+		self.vesting = vesting;
+		self
+	}
+
+	pub fn sett_n_jusd_registered(self) -> Self {
+		self.currencies(vec![(SETT, sett_metadata()), (JUSD, jusd_metadata())])
+	}
+
 	pub fn one_hundred_for_alice_n_bob_n_serper_n_settpay(self) -> Self {
 		self.balances(vec![
 			(ALICE, DNAR, 100), 
@@ -225,6 +365,13 @@ impl ExtBuilder {
 		.assimilate_storage(&mut t)
 		.unwrap();
 
+		stp258_currencies::GenesisConfig::<Runtime> {
+			currencies: self.currencies,
+			vesting: self.vesting,
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
 		t.into()
 	}
 }
@@ -3,9 +3,13 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
 use mock::{Event, *};
-use sp_runtime::traits::BadOrigin;
+use pallet_transaction_payment::OnChargeTransaction;
+use sp_runtime::{
+	traits::BadOrigin,
+	transaction_validity::{InvalidTransaction, TransactionValidityError},
+};
 
 #[test]
 fn stp258_currency_lockable_should_work() {
@@ -75,6 +79,35 @@ fn stp258_asset_adapting_pallet_balances_lockable() {
 		});
 }
 
+#[test]
+fn stp258_asset_adapting_pallet_balances_withdraw_reasons() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(AdaptedStp258Asset::set_lock_with_reasons(
+				ID_1,
+				&ALICE,
+				100,
+				WithdrawReasons::TRANSFER
+			));
+			assert!(AdaptedStp258Asset::ensure_can_withdraw(&ALICE, 10).is_err());
+			assert!(AdaptedStp258Asset::ensure_can_withdraw_with_reasons(&ALICE, 10, WithdrawReasons::TRANSFER).is_err());
+			assert_ok!(AdaptedStp258Asset::ensure_can_withdraw_with_reasons(
+				&ALICE,
+				10,
+				WithdrawReasons::TRANSACTION_PAYMENT
+			));
+			assert_ok!(AdaptedStp258Asset::withdraw_with_reasons(
+				&ALICE,
+				10,
+				WithdrawReasons::TRANSACTION_PAYMENT,
+				ExistenceRequirement::AllowDeath
+			));
+			assert_eq!(PalletBalances::free_balance(&ALICE), 90);
+		});
+}
+
 #[test]
 fn stp258_asset_adapting_pallet_balances_reservable() {
 	ExtBuilder::default()
@@ -86,6 +119,43 @@ fn stp258_asset_adapting_pallet_balances_reservable() {
 		});
 }
 
+#[test]
+fn stp258_asset_adapter_routes_slashed_funds_to_on_slash() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_eq!(TreasuryAdaptedStp258Asset::slash(&ALICE, 10), 0);
+			assert_eq!(PalletBalances::free_balance(&ALICE), 90);
+			assert_eq!(PalletBalances::free_balance(&TREASURY), 10);
+			assert_eq!(PalletBalances::total_issuance(), 400);
+
+			assert_ok!(TreasuryAdaptedStp258Asset::reserve(&ALICE, 20));
+			assert_eq!(TreasuryAdaptedStp258Asset::slash_reserved(&ALICE, 30), 10);
+			assert_eq!(PalletBalances::free_balance(&TREASURY), 30);
+			assert_eq!(PalletBalances::total_issuance(), 400);
+
+			assert_eq!(AdaptedStp258Asset::slash(&BOB, 10), 0);
+			assert_eq!(PalletBalances::free_balance(&TREASURY), 30);
+			assert_eq!(PalletBalances::total_issuance(), 390);
+		});
+}
+
+#[test]
+fn stp258_asset_adapter_hands_deposits_to_on_deposit() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			DEPOSITED.with(|v| *v.borrow_mut() = 0);
+			assert_ok!(TreasuryAdaptedStp258Asset::deposit(&ALICE, 10));
+			assert_ok!(TreasuryAdaptedStp258Asset::update_balance(&BOB, 5));
+			assert_eq!(DEPOSITED.with(|v| *v.borrow()), 15);
+			assert_eq!(PalletBalances::free_balance(&ALICE), 110);
+			assert_eq!(PalletBalances::total_issuance(), 415);
+		});
+}
+
 #[test]
 fn stp258_currency_should_work() {
 	ExtBuilder::default()
@@ -241,6 +311,26 @@ fn update_balance_call_fails_if_not_root_origin() {
 	});
 }
 
+#[test]
+fn update_balance_call_works_with_update_origin() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Currencies::update_balance(
+				Some(SERPER).into(),
+				ALICE,
+				JUSD,
+				-10 * 1_000
+			));
+			assert_eq!(Stp258Currencies::free_balance(JUSD, &ALICE), 90 * 1_000);
+			assert_noop!(
+				Stp258Currencies::update_balance(Some(BOB).into(), ALICE, JUSD, 10 * 1_000),
+				BadOrigin
+			);
+		});
+}
+
 #[test]
 fn call_event_should_work() {
 	ExtBuilder::default()
@@ -282,3 +372,1947 @@ fn call_event_should_work() {
 			assert!(System::events().iter().any(|record| record.event == transferred_event));
 		});
 }
+
+#[test]
+fn register_currency_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_eq!(Stp258Currencies::currency_metadata(SETT), None);
+		assert_ok!(Stp258Currencies::register_currency(Origin::root(), SETT, sett_metadata()));
+		assert_eq!(Stp258Currencies::currency_metadata(SETT), Some(sett_metadata()));
+
+		let registered_event = Event::stp258_currencies(crate::Event::CurrencyRegistered(SETT));
+		assert!(System::events().iter().any(|record| record.event == registered_event));
+	});
+}
+
+#[test]
+fn register_currency_fails_if_already_registered() {
+	ExtBuilder::default()
+		.sett_n_jusd_registered()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Stp258Currencies::register_currency(Origin::root(), SETT, sett_metadata()),
+				Error::<Runtime>::CurrencyAlreadyRegistered
+			);
+		});
+}
+
+#[test]
+fn register_currency_fails_if_not_root_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Stp258Currencies::register_currency(Some(ALICE).into(), SETT, sett_metadata()),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn update_currency_metadata_should_work() {
+	ExtBuilder::default()
+		.sett_n_jusd_registered()
+		.build()
+		.execute_with(|| {
+			let metadata = CurrencyMetadata {
+				decimals: 6,
+				..sett_metadata()
+			};
+			assert_ok!(Stp258Currencies::update_currency_metadata(
+				Origin::root(),
+				SETT,
+				metadata.clone()
+			));
+			assert_eq!(Stp258Currencies::currency_metadata(SETT), Some(metadata));
+
+			assert_noop!(
+				Stp258Currencies::update_currency_metadata(Origin::root(), DNAR, sett_metadata()),
+				Error::<Runtime>::CurrencyNotRegistered
+			);
+		});
+}
+
+#[test]
+fn base_unit_consults_registry() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(Stp258Currencies::base_unit(SETT), 10_000);
+		assert_eq!(Stp258Currencies::minimum_balance(SETT), 0);
+
+		let metadata = CurrencyMetadata {
+			decimals: 6,
+			minimum_balance: Some(100),
+			..sett_metadata()
+		};
+		assert_ok!(Stp258Currencies::register_currency(Origin::root(), SETT, metadata));
+		assert_eq!(Stp258Currencies::base_unit(SETT), 1_000_000);
+		assert_eq!(Stp258Currencies::minimum_balance(SETT), 0);
+		assert_eq!(
+			Stp258Currencies::currency_metadata(SETT).and_then(|metadata| metadata.minimum_balance),
+			Some(100)
+		);
+
+		assert_eq!(Stp258Currencies::base_unit(JUSD), 1_000);
+	});
+}
+
+#[test]
+fn transfer_all_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Stp258Currencies::transfer_all(Some(ALICE).into(), BOB, SETT, false));
+			assert_eq!(Stp258Currencies::free_balance(SETT, &ALICE), 0);
+			assert_eq!(Stp258Currencies::free_balance(SETT, &BOB), 200 * 10_000);
+
+			let transferred_event = Event::stp258_currencies(crate::Event::Transferred(SETT, ALICE, BOB, 100 * 10_000));
+			assert!(System::events().iter().any(|record| record.event == transferred_event));
+
+			assert_ok!(Stp258Currencies::transfer_all(Some(ALICE).into(), BOB, DNAR, true));
+			assert_eq!(Stp258Native::free_balance(&ALICE), 1);
+			assert_eq!(Stp258Native::free_balance(&BOB), 199);
+		});
+}
+
+#[test]
+fn transfer_all_respects_locks_and_reserves() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Currencies::set_lock(ID_1, SETT, &ALICE, 30 * 10_000));
+			assert_ok!(Stp258Currencies::reserve(SETT, &ALICE, 20 * 10_000));
+			assert_eq!(Stp258Currencies::transferable_balance(SETT, &ALICE, false), 50 * 10_000);
+			assert_ok!(Stp258Currencies::transfer_all(Some(ALICE).into(), BOB, SETT, false));
+			assert_eq!(Stp258Currencies::free_balance(SETT, &ALICE), 30 * 10_000);
+			assert_eq!(Stp258Currencies::free_balance(SETT, &BOB), 150 * 10_000);
+
+			assert_ok!(Stp258Currencies::set_lock(ID_1, DNAR, &ALICE, 30));
+			assert_ok!(Stp258Currencies::reserve(DNAR, &ALICE, 20));
+			assert_eq!(Stp258Currencies::transferable_balance(DNAR, &ALICE, false), 50);
+			assert_ok!(Stp258Currencies::transfer_all(Some(ALICE).into(), BOB, DNAR, false));
+			assert_eq!(Stp258Native::free_balance(&ALICE), 30);
+			assert_eq!(Stp258Native::free_balance(&BOB), 150);
+		});
+}
+
+#[test]
+fn transfer_keep_alive_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Stp258Currencies::transfer_native_currency_keep_alive(Some(ALICE).into(), BOB, 100),
+				pallet_balances::Error::<Runtime, _>::KeepAlive
+			);
+			assert_ok!(Stp258Currencies::transfer_native_currency_keep_alive(Some(ALICE).into(), BOB, 99));
+			assert_eq!(Stp258Native::free_balance(&ALICE), 1);
+			assert_eq!(Stp258Native::free_balance(&BOB), 199);
+
+			assert_noop!(
+				Stp258Currencies::transfer_keep_alive(Some(BOB).into(), ALICE, DNAR, 199),
+				pallet_balances::Error::<Runtime, _>::KeepAlive
+			);
+			assert_ok!(Stp258Currencies::transfer_keep_alive(Some(BOB).into(), ALICE, DNAR, 198));
+			assert_eq!(Stp258Native::free_balance(&BOB), 1);
+		});
+}
+
+#[test]
+fn transfer_keep_alive_non_native_respects_existential_deposit() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Stp258Currencies::transfer_keep_alive(Some(ALICE).into(), BOB, JUSD, 100 * 1_000 - 99),
+				Error::<Runtime>::KeepAlive
+			);
+			assert_ok!(Stp258Currencies::transfer_keep_alive(Some(ALICE).into(), BOB, JUSD, 100 * 1_000 - 100));
+			assert_eq!(Stp258Currencies::free_balance(JUSD, &ALICE), 100);
+
+			assert_ok!(Stp258Currencies::transfer(Some(ALICE).into(), BOB, JUSD, 100));
+			assert_eq!(Stp258Currencies::free_balance(JUSD, &ALICE), 0);
+		});
+}
+
+#[test]
+fn stp258_asset_adapting_pallet_balances_transfer_with_existence() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				AdaptedStp258Asset::transfer_with_existence(&ALICE, &BOB, 100, ExistenceRequirement::KeepAlive),
+				pallet_balances::Error::<Runtime, _>::KeepAlive
+			);
+			assert_ok!(AdaptedStp258Asset::transfer_with_existence(
+				&ALICE,
+				&BOB,
+				100,
+				ExistenceRequirement::AllowDeath
+			));
+			assert_eq!(PalletBalances::total_balance(&ALICE), 0);
+		});
+}
+
+#[test]
+fn transfer_batch_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Stp258Currencies::transfer_batch(
+				Some(ALICE).into(),
+				vec![(BOB, SETT, 10 * 10_000), (SERPER, JUSD, 20 * 1_000), (BOB, DNAR, 30)]
+			));
+			assert_eq!(Stp258Currencies::free_balance(SETT, &ALICE), 90 * 10_000);
+			assert_eq!(Stp258Currencies::free_balance(SETT, &BOB), 110 * 10_000);
+			assert_eq!(Stp258Currencies::free_balance(JUSD, &ALICE), 80 * 1_000);
+			assert_eq!(Stp258Currencies::free_balance(JUSD, &SERPER), 120 * 1_000);
+			assert_eq!(Stp258Native::free_balance(&ALICE), 70);
+			assert_eq!(Stp258Native::free_balance(&BOB), 130);
+
+			for event in vec![
+				crate::Event::Transferred(SETT, ALICE, BOB, 10 * 10_000),
+				crate::Event::Transferred(JUSD, ALICE, SERPER, 20 * 1_000),
+				crate::Event::Transferred(DNAR, ALICE, BOB, 30),
+			] {
+				let transferred_event = Event::stp258_currencies(event);
+				assert!(System::events().iter().any(|record| record.event == transferred_event));
+			}
+		});
+}
+
+#[test]
+fn transfer_batch_is_atomic() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Stp258Currencies::transfer_batch(
+					Some(ALICE).into(),
+					vec![(BOB, SETT, 10 * 10_000), (BOB, DNAR, 101)]
+				),
+				pallet_balances::Error::<Runtime, _>::InsufficientBalance
+			);
+			assert_eq!(Stp258Currencies::free_balance(SETT, &ALICE), 100 * 10_000);
+		});
+}
+
+#[test]
+fn transfer_batch_fails_if_too_many_transfers() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Stp258Currencies::transfer_batch(Some(ALICE).into(), vec![(BOB, DNAR, 1); 5]),
+				Error::<Runtime>::TooManyTransfers
+			);
+		});
+}
+
+#[test]
+fn approve_and_transfer_from_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Stp258Currencies::approve(Some(ALICE).into(), BOB, SETT, 30 * 10_000));
+			assert_eq!(Stp258Currencies::allowance(SETT, &ALICE, &BOB), 30 * 10_000);
+			assert_eq!(Stp258Currencies::allowance(JUSD, &ALICE, &BOB), 0);
+
+			let approval_event = Event::stp258_currencies(crate::Event::Approval(SETT, ALICE, BOB, 30 * 10_000));
+			assert!(System::events().iter().any(|record| record.event == approval_event));
+
+			assert_ok!(Stp258Currencies::transfer_from(
+				Some(BOB).into(),
+				ALICE,
+				SERPER,
+				SETT,
+				20 * 10_000
+			));
+			assert_eq!(Stp258Currencies::allowance(SETT, &ALICE, &BOB), 10 * 10_000);
+			assert_eq!(Stp258Currencies::free_balance(SETT, &ALICE), 80 * 10_000);
+			assert_eq!(Stp258Currencies::free_balance(SETT, &SERPER), 120 * 10_000);
+
+			let transferred_event = Event::stp258_currencies(crate::Event::Transferred(SETT, ALICE, SERPER, 20 * 10_000));
+			assert!(System::events().iter().any(|record| record.event == transferred_event));
+
+			assert_noop!(
+				Stp258Currencies::transfer_from(Some(BOB).into(), ALICE, BOB, SETT, 10 * 10_000 + 1),
+				Error::<Runtime>::AllowanceExceeded
+			);
+			assert_noop!(
+				Stp258Currencies::transfer_from(Some(SERPER).into(), ALICE, SERPER, SETT, 1),
+				Error::<Runtime>::AllowanceExceeded
+			);
+		});
+}
+
+#[test]
+fn transfer_from_spends_allowance_on_transfer_fee() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Currencies::set_transfer_fee(Origin::root(), SETT, Some(sett_transfer_fee())));
+			assert_ok!(Stp258Currencies::approve(Some(ALICE).into(), BOB, SETT, 30 * 10_000));
+
+			assert_ok!(Stp258Currencies::transfer_from(Some(BOB).into(), ALICE, BOB, SETT, 20 * 10_000));
+			assert_eq!(Stp258Currencies::allowance(SETT, &ALICE, &BOB), 10 * 10_000 - 2_000);
+			assert_eq!(Stp258Currencies::free_balance(SETT, &ALICE), 80 * 10_000 - 2_000);
+
+			assert_noop!(
+				Stp258Currencies::transfer_from(Some(BOB).into(), ALICE, BOB, SETT, 10 * 10_000 - 2_000),
+				Error::<Runtime>::AllowanceExceeded
+			);
+			assert_ok!(Stp258Currencies::transfer_from(Some(BOB).into(), ALICE, BOB, SETT, 97_000));
+			assert_eq!(Stp258Currencies::allowance(SETT, &ALICE, &BOB), 30);
+		});
+}
+
+#[test]
+fn increase_and_decrease_allowance_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Currencies::increase_allowance(Some(ALICE).into(), BOB, DNAR, 10));
+			assert_ok!(Stp258Currencies::increase_allowance(Some(ALICE).into(), BOB, DNAR, 20));
+			assert_eq!(Stp258Currencies::allowance(DNAR, &ALICE, &BOB), 30);
+
+			assert_ok!(Stp258Currencies::decrease_allowance(Some(ALICE).into(), BOB, DNAR, 25));
+			assert_eq!(Stp258Currencies::allowance(DNAR, &ALICE, &BOB), 5);
+			assert_noop!(
+				Stp258Currencies::decrease_allowance(Some(ALICE).into(), BOB, DNAR, 6),
+				Error::<Runtime>::AllowanceExceeded
+			);
+
+			assert_ok!(Stp258Currencies::transfer_from(Some(BOB).into(), ALICE, BOB, DNAR, 5));
+			assert_eq!(Stp258Native::free_balance(&BOB), 105);
+			assert!(!Allowances::<Runtime>::contains_key(ALICE, (BOB, DNAR)));
+		});
+}
+
+#[test]
+fn lock_events_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Stp258Currencies::set_lock(ID_1, SETT, &ALICE, 50 * 10_000));
+			let lock_event = Event::stp258_currencies(crate::Event::LockSet(ID_1, SETT, ALICE, 50 * 10_000));
+			assert!(System::events().iter().any(|record| record.event == lock_event));
+
+			assert_ok!(Stp258Currencies::extend_lock(ID_1, DNAR, &ALICE, 20));
+			let lock_event = Event::stp258_currencies(crate::Event::LockExtended(ID_1, DNAR, ALICE, 20));
+			assert!(System::events().iter().any(|record| record.event == lock_event));
+
+			assert_ok!(Stp258Currencies::remove_lock(ID_1, SETT, &ALICE));
+			let lock_event = Event::stp258_currencies(crate::Event::LockRemoved(ID_1, SETT, ALICE));
+			assert!(System::events().iter().any(|record| record.event == lock_event));
+		});
+}
+
+#[test]
+fn force_set_and_remove_lock_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Currencies::force_set_lock(Origin::root(), ALICE, SETT, ID_1, 50 * 10_000));
+			assert_eq!(Stp258Serp::locks(&ALICE, SETT).len(), 1);
+			assert_ok!(Stp258Currencies::force_set_lock(Origin::root(), ALICE, DNAR, ID_1, 50));
+			assert_eq!(PalletBalances::locks(&ALICE).len(), 1);
+
+			assert_ok!(Stp258Currencies::force_remove_lock(Origin::root(), ALICE, SETT, ID_1));
+			assert_eq!(Stp258Serp::locks(&ALICE, SETT).len(), 0);
+			assert_ok!(Stp258Currencies::force_remove_lock(Origin::root(), ALICE, DNAR, ID_1));
+			assert_eq!(PalletBalances::locks(&ALICE).len(), 0);
+
+			assert_noop!(
+				Stp258Currencies::force_set_lock(Some(ALICE).into(), ALICE, SETT, ID_1, 50 * 10_000),
+				BadOrigin
+			);
+		});
+}
+
+#[test]
+fn reserve_events_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Stp258Currencies::reserve(SETT, &ALICE, 30 * 10_000));
+			let reserve_event = Event::stp258_currencies(crate::Event::Reserved(SETT, ALICE, 30 * 10_000));
+			assert!(System::events().iter().any(|record| record.event == reserve_event));
+
+			assert_eq!(Stp258Currencies::unreserve(SETT, &ALICE, 10 * 10_000), 0);
+			let reserve_event = Event::stp258_currencies(crate::Event::Unreserved(SETT, ALICE, 10 * 10_000));
+			assert!(System::events().iter().any(|record| record.event == reserve_event));
+
+			assert_eq!(
+				Stp258Currencies::repatriate_reserved(SETT, &ALICE, &BOB, 15 * 10_000, BalanceStatus::Free),
+				Ok(0)
+			);
+			let reserve_event = Event::stp258_currencies(crate::Event::ReserveRepatriated(
+				SETT,
+				ALICE,
+				BOB,
+				15 * 10_000,
+				BalanceStatus::Free,
+			));
+			assert!(System::events().iter().any(|record| record.event == reserve_event));
+
+			assert_eq!(Stp258Currencies::slash_reserved(SETT, &ALICE, 10 * 10_000), 5 * 10_000);
+			let reserve_event = Event::stp258_currencies(crate::Event::ReserveSlashed(SETT, ALICE, 5 * 10_000));
+			assert!(System::events().iter().any(|record| record.event == reserve_event));
+		});
+}
+
+#[test]
+fn native_reserve_events_report_actual_amounts() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Stp258Currencies::reserve(DNAR, &ALICE, 40));
+			assert_eq!(Stp258Currencies::unreserve(DNAR, &ALICE, 50), 10);
+			let reserve_event = Event::stp258_currencies(crate::Event::Unreserved(DNAR, ALICE, 40));
+			assert!(System::events().iter().any(|record| record.event == reserve_event));
+
+			assert_ok!(Stp258Currencies::reserve(DNAR, &ALICE, 20));
+			assert_eq!(
+				Stp258Currencies::repatriate_reserved(DNAR, &ALICE, &BOB, 30, BalanceStatus::Reserved),
+				Ok(10)
+			);
+			assert_eq!(Stp258Currencies::reserved_balance(DNAR, &BOB), 20);
+			let reserve_event = Event::stp258_currencies(crate::Event::ReserveRepatriated(
+				DNAR,
+				ALICE,
+				BOB,
+				20,
+				BalanceStatus::Reserved,
+			));
+			assert!(System::events().iter().any(|record| record.event == reserve_event));
+		});
+}
+
+#[test]
+fn paused_currency_cannot_move() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Stp258Currencies::pause_currency(Origin::root(), SETT));
+			assert!(Stp258Currencies::is_paused(SETT));
+			let pause_event = Event::stp258_currencies(crate::Event::CurrencyPaused(SETT));
+			assert!(System::events().iter().any(|record| record.event == pause_event));
+
+			assert_noop!(
+				Stp258Currencies::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000),
+				Error::<Runtime>::CurrencyPaused
+			);
+			assert_noop!(
+				<Stp258Currencies as Stp258Currency<AccountId>>::deposit(SETT, &ALICE, 10 * 10_000),
+				Error::<Runtime>::CurrencyPaused
+			);
+			assert_noop!(
+				<Stp258Currencies as Stp258Currency<AccountId>>::withdraw(SETT, &ALICE, 10 * 10_000),
+				Error::<Runtime>::CurrencyPaused
+			);
+			assert_ok!(Stp258Currencies::transfer(Some(ALICE).into(), BOB, JUSD, 10 * 1_000));
+
+			assert_ok!(Stp258Currencies::unpause_currency(Origin::root(), SETT));
+			assert!(!Stp258Currencies::is_paused(SETT));
+			assert_ok!(Stp258Currencies::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000));
+		});
+}
+
+#[test]
+fn pause_currency_fails_for_native_currency() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Stp258Currencies::pause_currency(Origin::root(), DNAR),
+				Error::<Runtime>::CannotPauseNativeCurrency
+			);
+			assert!(!Stp258Currencies::is_paused(DNAR));
+			assert_ok!(Stp258Currencies::transfer_native_currency(Some(ALICE).into(), BOB, 10));
+		});
+}
+
+#[test]
+fn pause_currency_fails_if_not_pause_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(Stp258Currencies::pause_currency(Some(ALICE).into(), SETT), BadOrigin);
+		assert_noop!(Stp258Currencies::unpause_currency(Some(ALICE).into(), SETT), BadOrigin);
+	});
+}
+
+#[test]
+fn frozen_account_cannot_move_balance() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Stp258Currencies::reserve(JUSD, &ALICE, 10 * 1_000));
+			assert_ok!(Stp258Currencies::freeze_account(Origin::root(), ALICE, JUSD));
+			assert!(Stp258Currencies::is_frozen(JUSD, &ALICE));
+			let freeze_event = Event::stp258_currencies(crate::Event::AccountFrozen(JUSD, ALICE));
+			assert!(System::events().iter().any(|record| record.event == freeze_event));
+
+			assert_noop!(
+				Stp258Currencies::transfer(Some(ALICE).into(), BOB, JUSD, 10 * 1_000),
+				Error::<Runtime>::AccountFrozen
+			);
+			assert_noop!(
+				<Stp258Currencies as Stp258Currency<AccountId>>::withdraw(JUSD, &ALICE, 10 * 1_000),
+				Error::<Runtime>::AccountFrozen
+			);
+			assert_noop!(
+				Stp258Currencies::reserve(JUSD, &ALICE, 10 * 1_000),
+				Error::<Runtime>::AccountFrozen
+			);
+			assert_noop!(
+				Stp258Currencies::repatriate_reserved(JUSD, &ALICE, &BOB, 10 * 1_000, BalanceStatus::Free),
+				Error::<Runtime>::AccountFrozen
+			);
+			assert_noop!(
+				Stp258Currencies::merge_account(&ALICE, &BOB),
+				Error::<Runtime>::AccountFrozen
+			);
+
+			// other currencies and incoming transfers are unaffected
+			assert_ok!(Stp258Currencies::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000));
+			assert_ok!(Stp258Currencies::transfer(Some(BOB).into(), ALICE, JUSD, 10 * 1_000));
+
+			assert_ok!(Stp258Currencies::thaw_account(Origin::root(), ALICE, JUSD));
+			assert!(!Stp258Currencies::is_frozen(JUSD, &ALICE));
+			assert_ok!(Stp258Currencies::transfer(Some(ALICE).into(), BOB, JUSD, 10 * 1_000));
+		});
+}
+
+#[test]
+fn freeze_account_fails_if_not_freeze_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(Stp258Currencies::freeze_account(Some(ALICE).into(), BOB, JUSD), BadOrigin);
+		assert_noop!(Stp258Currencies::thaw_account(Some(ALICE).into(), BOB, JUSD), BadOrigin);
+	});
+}
+
+#[test]
+fn transfer_call_refunds_weight() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			let noop_weight = <Runtime as frame_system::Config>::DbWeight::get().reads(2);
+
+			let post_info = Stp258Currencies::transfer(Some(ALICE).into(), BOB, SETT, 0).unwrap();
+			assert_eq!(post_info.actual_weight, Some(noop_weight));
+			let post_info = Stp258Currencies::transfer(Some(ALICE).into(), ALICE, SETT, 10 * 10_000).unwrap();
+			assert_eq!(post_info.actual_weight, Some(noop_weight));
+
+			let post_info = Stp258Currencies::transfer(Some(ALICE).into(), BOB, SETT, 10 * 10_000).unwrap();
+			assert_eq!(
+				post_info.actual_weight,
+				Some(<() as WeightInfo>::transfer_non_native_currency())
+			);
+			let post_info = Stp258Currencies::transfer(Some(ALICE).into(), BOB, DNAR, 10).unwrap();
+			assert_eq!(post_info.actual_weight, Some(<() as WeightInfo>::transfer_native_currency()));
+			let post_info = Stp258Currencies::transfer_native_currency(Some(ALICE).into(), BOB, 10).unwrap();
+			assert_eq!(post_info.actual_weight, Some(<() as WeightInfo>::transfer_native_currency()));
+		});
+}
+
+#[test]
+fn update_balance_call_refunds_weight() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			let noop_weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+			let post_info = Stp258Currencies::update_balance(Origin::root(), ALICE, SETT, 0).unwrap();
+			assert_eq!(post_info.actual_weight, Some(noop_weight));
+			let post_info = Stp258Currencies::update_balance(Origin::root(), ALICE, DNAR, 0).unwrap();
+			assert_eq!(post_info.actual_weight, Some(noop_weight));
+			assert_eq!(Stp258Currencies::free_balance(DNAR, &ALICE), 100);
+
+			let post_info = Stp258Currencies::update_balance(Origin::root(), ALICE, SETT, 10).unwrap();
+			assert_eq!(
+				post_info.actual_weight,
+				Some(<() as WeightInfo>::update_balance_non_native_currency())
+			);
+			let post_info =
+				Stp258Currencies::update_balance(Origin::root(), ALICE, SETT, -(100 * 10_000 + 10)).unwrap();
+			assert_eq!(
+				post_info.actual_weight,
+				Some(<() as WeightInfo>::update_balance_non_native_currency_killing())
+			);
+			let post_info = Stp258Currencies::update_balance(Origin::root(), BOB, DNAR, -10).unwrap();
+			assert_eq!(
+				post_info.actual_weight,
+				Some(<() as WeightInfo>::update_balance_native_currency_creating())
+			);
+			let post_info = Stp258Currencies::update_balance(Origin::root(), ALICE, DNAR, 10).unwrap();
+			assert_eq!(
+				post_info.actual_weight,
+				Some(<() as WeightInfo>::update_balance_native_currency_creating())
+			);
+			let post_info = Stp258Currencies::update_balance(Origin::root(), ALICE, DNAR, -110).unwrap();
+			assert_eq!(
+				post_info.actual_weight,
+				Some(<() as WeightInfo>::update_balance_native_currency_killing())
+			);
+		});
+}
+
+#[test]
+fn locks_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Currencies::set_lock(ID_1, DNAR, &ALICE, 50));
+			assert_ok!(Stp258Currencies::set_lock(ID_1, SETT, &ALICE, 50 * 10_000));
+
+			assert_eq!(Stp258Currencies::locks(DNAR, &ALICE), vec![(ID_1, 50)]);
+			assert_eq!(Stp258Currencies::locks(SETT, &ALICE), vec![(ID_1, 50 * 10_000)]);
+			assert_eq!(Stp258Currencies::locks(JUSD, &ALICE), vec![]);
+		});
+}
+
+#[test]
+fn all_balances_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.currencies(vec![(SETT, sett_metadata())])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Currencies::reserve(SETT, &ALICE, 30 * 10_000));
+			assert_eq!(
+				Stp258Currencies::all_balances(&ALICE),
+				vec![(DNAR, 100, 0), (SETT, 70 * 10_000, 30 * 10_000)]
+			);
+		});
+}
+
+#[test]
+fn setheum_currency_adapter_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_eq!(SettCurrency::total_issuance(), 400 * 10_000);
+			assert_eq!(SettCurrency::free_balance(&ALICE), 100 * 10_000);
+
+			assert_ok!(SettCurrency::transfer(&ALICE, &BOB, 10 * 10_000, ExistenceRequirement::AllowDeath));
+			assert_eq!(SettCurrency::free_balance(&ALICE), 90 * 10_000);
+			assert_eq!(SettCurrency::free_balance(&BOB), 110 * 10_000);
+			assert_noop!(
+				SettCurrency::transfer(&ALICE, &BOB, 90 * 10_000, ExistenceRequirement::KeepAlive),
+				Error::<Runtime>::KeepAlive
+			);
+
+			let imbalance = SettCurrency::deposit_creating(&ALICE, 10 * 10_000);
+			assert_eq!(imbalance.peek(), 10 * 10_000);
+			drop(imbalance);
+			assert_eq!(SettCurrency::free_balance(&ALICE), 100 * 10_000);
+			assert_eq!(SettCurrency::total_issuance(), 410 * 10_000);
+
+			let imbalance =
+				SettCurrency::withdraw(&ALICE, 20 * 10_000, WithdrawReasons::all(), ExistenceRequirement::KeepAlive)
+					.unwrap();
+			assert_eq!(imbalance.peek(), 20 * 10_000);
+			drop(imbalance);
+			assert_eq!(SettCurrency::free_balance(&ALICE), 80 * 10_000);
+			assert_eq!(SettCurrency::total_issuance(), 390 * 10_000);
+
+			let (imbalance, remaining) = SettCurrency::slash(&ALICE, 100 * 10_000);
+			assert_eq!(imbalance.peek(), 80 * 10_000);
+			assert_eq!(remaining, 20 * 10_000);
+			drop(imbalance);
+			assert_eq!(SettCurrency::total_issuance(), 310 * 10_000);
+		});
+}
+
+#[test]
+fn setheum_currency_adapter_transfers_without_fees_pause_or_freeze() {
+	// how a stock pallet, e.g. a treasury paying out, moves the balance
+	fn pay_out<C: SetheumCurrency<AccountId>>(from: &AccountId, to: &AccountId, amount: C::Balance) -> DispatchResult {
+		C::transfer(from, to, amount, ExistenceRequirement::KeepAlive)
+	}
+
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Currencies::set_transfer_fee(
+				Origin::root(),
+				SETT,
+				Some(sett_transfer_fee())
+			));
+			assert_ok!(Stp258Currencies::pause_currency(Origin::root(), SETT));
+			assert_ok!(Stp258Currencies::freeze_account(Origin::root(), ALICE, SETT));
+
+			assert_ok!(pay_out::<SettCurrency>(&ALICE, &BOB, 40 * 10_000));
+			assert_eq!(SettCurrency::free_balance(&ALICE), 60 * 10_000);
+			assert_eq!(SettCurrency::free_balance(&BOB), 140 * 10_000);
+			assert_eq!(SettCurrency::free_balance(&SERPER), 100 * 10_000);
+			assert_eq!(SettCurrency::free_balance(&SETTPAY), 100 * 10_000);
+
+			assert_eq!(<SettCurrency as SetheumLockableCurrency<AccountId>>::MaxLocks::get(), 50);
+		});
+}
+
+#[test]
+fn setheum_currency_adapter_issue_and_burn_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			let issued = SettCurrency::issue(10 * 10_000);
+			assert_eq!(issued.peek(), 10 * 10_000);
+			assert_eq!(SettCurrency::total_issuance(), 410 * 10_000);
+			SettCurrency::resolve_creating(&ALICE, issued);
+			assert_eq!(SettCurrency::free_balance(&ALICE), 110 * 10_000);
+			assert_eq!(SettCurrency::total_issuance(), 410 * 10_000);
+
+			let burned = SettCurrency::burn(10 * 10_000);
+			assert_eq!(SettCurrency::total_issuance(), 400 * 10_000);
+			drop(burned);
+			assert_eq!(SettCurrency::total_issuance(), 410 * 10_000);
+			assert_eq!(SettCurrency::burn(500 * 10_000).peek(), 410 * 10_000);
+			assert_eq!(SettCurrency::total_issuance(), 410 * 10_000);
+
+			let burned = SettCurrency::burn(10 * 10_000);
+			let withdrawn =
+				SettCurrency::withdraw(&ALICE, 10 * 10_000, WithdrawReasons::all(), ExistenceRequirement::KeepAlive)
+					.unwrap();
+			match burned.offset(withdrawn) {
+				Ok(imbalance) => assert_eq!(imbalance.peek(), 0),
+				Err(_) => panic!("expected a positive imbalance"),
+			}
+			assert_eq!(SettCurrency::free_balance(&ALICE), 100 * 10_000);
+			assert_eq!(SettCurrency::total_issuance(), 400 * 10_000);
+			assert_eq!(PendingIssuance::<Runtime>::get(SETT), (0, 0));
+		});
+}
+
+#[test]
+fn setheum_currency_adapter_issuance_changes_when_imbalances_drop() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			let deposited = SettCurrency::deposit_creating(&ALICE, 10 * 10_000);
+			assert_eq!(SettCurrency::free_balance(&ALICE), 110 * 10_000);
+			assert_eq!(SettCurrency::total_issuance(), 400 * 10_000);
+			let (first, second) = deposited.split(4 * 10_000);
+			drop(first);
+			assert_eq!(SettCurrency::total_issuance(), 404 * 10_000);
+			drop(second);
+			assert_eq!(SettCurrency::total_issuance(), 410 * 10_000);
+
+			let withdrawn =
+				SettCurrency::withdraw(&ALICE, 20 * 10_000, WithdrawReasons::all(), ExistenceRequirement::KeepAlive)
+					.unwrap();
+			assert_eq!(SettCurrency::total_issuance(), 410 * 10_000);
+			SettCurrency::resolve_creating(&BOB, withdrawn);
+			assert_eq!(SettCurrency::free_balance(&BOB), 120 * 10_000);
+			assert_eq!(SettCurrency::total_issuance(), 410 * 10_000);
+			assert_eq!(PendingIssuance::<Runtime>::get(SETT), (0, 0));
+		});
+}
+
+#[test]
+fn setheum_currency_adapter_deposit_into_existing_requires_account() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			let eve: AccountId = [9u8; 32].into();
+			assert_noop!(
+				SettCurrency::deposit_into_existing(&eve, 10).map(|imbalance| imbalance.peek()),
+				Error::<Runtime>::DeadAccount
+			);
+			assert_eq!(
+				SettCurrency::deposit_into_existing(&ALICE, 10).map(|imbalance| imbalance.peek()),
+				Ok(10)
+			);
+
+			match SettCurrency::make_free_balance_be(&ALICE, 50 * 10_000) {
+				SignedImbalance::Negative(imbalance) => assert_eq!(imbalance.peek(), 50 * 10_000 + 10),
+				SignedImbalance::Positive(_) => panic!("expected a negative imbalance"),
+			}
+			assert_eq!(SettCurrency::free_balance(&ALICE), 50 * 10_000);
+		});
+}
+
+#[test]
+fn setheum_currency_adapter_reservable_and_lockable_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert!(SettCurrency::can_reserve(&ALICE, 50 * 10_000));
+			assert_ok!(SettCurrency::reserve(&ALICE, 50 * 10_000));
+			assert_eq!(SettCurrency::reserved_balance(&ALICE), 50 * 10_000);
+
+			let (imbalance, remaining) = SettCurrency::slash_reserved(&ALICE, 20 * 10_000);
+			assert_eq!(imbalance.peek(), 20 * 10_000);
+			assert_eq!(remaining, 0);
+			drop(imbalance);
+			assert_eq!(SettCurrency::total_issuance(), 380 * 10_000);
+
+			assert_eq!(
+				SettCurrency::repatriate_reserved(&ALICE, &BOB, 10 * 10_000, BalanceStatus::Free),
+				Ok(0)
+			);
+			assert_eq!(SettCurrency::free_balance(&BOB), 110 * 10_000);
+			assert_eq!(SettCurrency::unreserve(&ALICE, 30 * 10_000), 10 * 10_000);
+
+			SettCurrency::set_lock(ID_1, &ALICE, 50 * 10_000, WithdrawReasons::all());
+			assert_eq!(Stp258Serp::locks(&ALICE, SETT).len(), 1);
+			assert!(
+				SettCurrency::ensure_can_withdraw(&ALICE, 30 * 10_000, WithdrawReasons::all(), 40 * 10_000).is_err()
+			);
+			SettCurrency::remove_lock(ID_1, &ALICE);
+			assert_eq!(Stp258Serp::locks(&ALICE, SETT).len(), 0);
+		});
+}
+
+#[test]
+fn stp258_currency_withdraw_reasons_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Stp258Currencies::set_lock_with_reasons(
+				ID_1,
+				DNAR,
+				&ALICE,
+				100,
+				WithdrawReasons::TRANSFER
+			));
+			assert!(System::events().iter().any(|record| record.event
+				== Event::stp258_currencies(crate::Event::LockSet(ID_1, DNAR, ALICE, 100))));
+			assert_noop!(
+				Stp258Currencies::transfer(Some(ALICE).into(), BOB, DNAR, 10),
+				pallet_balances::Error::<Runtime, _>::LiquidityRestrictions
+			);
+			assert_ok!(Stp258Currencies::withdraw_with_reasons(
+				DNAR,
+				&ALICE,
+				10,
+				WithdrawReasons::TRANSACTION_PAYMENT,
+				ExistenceRequirement::AllowDeath
+			));
+			assert_eq!(Stp258Currencies::free_balance(DNAR, &ALICE), 90);
+
+			assert_ok!(Stp258Currencies::extend_lock_with_reasons(
+				ID_1,
+				DNAR,
+				&ALICE,
+				90,
+				WithdrawReasons::TRANSACTION_PAYMENT
+			));
+			assert!(Stp258Currencies::ensure_can_withdraw_with_reasons(
+				DNAR,
+				&ALICE,
+				10,
+				WithdrawReasons::TRANSACTION_PAYMENT
+			)
+			.is_err());
+
+			// reasons do not apply to non-native currencies
+			assert_ok!(Stp258Currencies::set_lock_with_reasons(
+				ID_1,
+				SETT,
+				&ALICE,
+				100 * 10_000,
+				WithdrawReasons::TRANSFER
+			));
+			assert!(Stp258Currencies::ensure_can_withdraw_with_reasons(
+				SETT,
+				&ALICE,
+				10,
+				WithdrawReasons::TRANSACTION_PAYMENT
+			)
+			.is_err());
+		});
+}
+
+#[test]
+fn setheum_currency_adapter_passes_withdraw_reasons() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			type DnarCurrency = SetheumCurrencyAdapter<Runtime, GetStp258NativeId>;
+			DnarCurrency::set_lock(ID_1, &ALICE, 100, WithdrawReasons::TRANSFER);
+			assert_ok!(DnarCurrency::ensure_can_withdraw(
+				&ALICE,
+				10,
+				WithdrawReasons::TRANSACTION_PAYMENT,
+				90
+			));
+			assert!(DnarCurrency::ensure_can_withdraw(&ALICE, 10, WithdrawReasons::TRANSFER, 90).is_err());
+		});
+}
+
+#[test]
+fn named_reserves_should_not_collide() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			let first: ReserveIdentifier = *b"first   ";
+			let second: ReserveIdentifier = *b"second  ";
+
+			assert_ok!(Stp258Currencies::reserve_named(&first, SETT, &ALICE, 30 * 10_000));
+			assert_ok!(Stp258Currencies::reserve_named(&second, SETT, &ALICE, 20 * 10_000));
+			assert_ok!(Stp258Currencies::reserve_named(&first, DNAR, &ALICE, 10));
+			assert_eq!(Stp258Currencies::reserved_balance_named(&first, SETT, &ALICE), 30 * 10_000);
+			assert_eq!(Stp258Currencies::reserved_balance_named(&second, SETT, &ALICE), 20 * 10_000);
+			assert_eq!(Stp258Currencies::reserved_balance_named(&first, DNAR, &ALICE), 10);
+			assert_eq!(Stp258Currencies::reserved_balance(SETT, &ALICE), 50 * 10_000);
+			assert_eq!(Stp258Currencies::reserved_balance(DNAR, &ALICE), 10);
+
+			assert_eq!(Stp258Currencies::unreserve_named(&first, SETT, &ALICE, 40 * 10_000), 10 * 10_000);
+			assert_eq!(Stp258Currencies::reserved_balance_named(&first, SETT, &ALICE), 0);
+			assert_eq!(Stp258Currencies::reserved_balance_named(&second, SETT, &ALICE), 20 * 10_000);
+			assert_eq!(Stp258Currencies::reserved_balance(SETT, &ALICE), 20 * 10_000);
+			assert_eq!(Stp258Currencies::free_balance(SETT, &ALICE), 80 * 10_000);
+			assert!(!NamedReserves::<Runtime>::contains_key(ALICE, (SETT, first)));
+
+			assert_eq!(Stp258Currencies::slash_reserved_named(&first, DNAR, &ALICE, 20), 10);
+			assert_eq!(Stp258Currencies::reserved_balance_named(&first, DNAR, &ALICE), 0);
+			assert_eq!(Stp258Currencies::total_issuance(DNAR), 390);
+		});
+}
+
+#[test]
+fn repatriate_reserved_named_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			let id: ReserveIdentifier = *b"escrow  ";
+
+			assert_ok!(Stp258Currencies::reserve_named(&id, SETT, &ALICE, 50 * 10_000));
+			assert_eq!(
+				Stp258Currencies::repatriate_reserved_named(
+					&id,
+					SETT,
+					&ALICE,
+					&BOB,
+					20 * 10_000,
+					BalanceStatus::Reserved
+				),
+				Ok(0)
+			);
+			assert_eq!(Stp258Currencies::reserved_balance_named(&id, SETT, &ALICE), 30 * 10_000);
+			assert_eq!(Stp258Currencies::reserved_balance_named(&id, SETT, &BOB), 20 * 10_000);
+			assert_eq!(Stp258Currencies::reserved_balance(SETT, &BOB), 20 * 10_000);
+
+			assert_eq!(
+				Stp258Currencies::repatriate_reserved_named(&id, SETT, &ALICE, &BOB, 40 * 10_000, BalanceStatus::Free),
+				Ok(10 * 10_000)
+			);
+			assert_eq!(Stp258Currencies::reserved_balance_named(&id, SETT, &ALICE), 0);
+			assert_eq!(Stp258Currencies::free_balance(SETT, &BOB), 130 * 10_000);
+		});
+}
+
+#[test]
+fn merge_account_fails_with_named_reserves() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			let id: ReserveIdentifier = *b"first   ";
+
+			assert_ok!(Stp258Currencies::reserve_named(&id, SETT, &ALICE, 30 * 10_000));
+			assert_noop!(
+				Stp258Currencies::merge_account(&ALICE, &BOB),
+				Error::<Runtime>::NamedReservesExist
+			);
+
+			assert_eq!(Stp258Currencies::unreserve_named(&id, SETT, &ALICE, 30 * 10_000), 0);
+			assert_ok!(Stp258Currencies::merge_account(&ALICE, &BOB));
+			assert_eq!(Stp258Currencies::free_balance(SETT, &BOB), 200 * 10_000);
+		});
+}
+
+#[test]
+fn stp258_asset_named_reservable_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			let id: ReserveIdentifier = *b"native  ";
+
+			assert_ok!(Stp258Native::reserve_named(&id, &ALICE, 50));
+			assert_eq!(Stp258Native::reserved_balance_named(&id, &ALICE), 50);
+			assert_eq!(Stp258Native::reserved_balance(&ALICE), 50);
+			assert_eq!(Stp258Native::unreserve_named(&id, &ALICE, 20), 0);
+			assert_eq!(Stp258Native::reserved_balance_named(&id, &ALICE), 30);
+			assert_eq!(Stp258Native::free_balance(&ALICE), 70);
+		});
+}
+
+type FeeCharger = <Runtime as pallet_transaction_payment::Config>::OnChargeTransaction;
+
+fn remark_call() -> Call {
+	Call::System(frame_system::Call::remark(vec![]))
+}
+
+#[test]
+fn set_fee_currency_should_work() {
+	ExtBuilder::default()
+		.sett_n_jusd_registered()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Stp258Currencies::set_fee_currency(Some(ALICE).into(), Some(SETT)));
+			assert_eq!(FeeCurrency::<Runtime>::get(&ALICE), Some(SETT));
+			assert!(System::events().iter().any(|record| record.event
+				== Event::stp258_currencies(crate::Event::FeeCurrencySet(ALICE, Some(SETT)))));
+
+			assert_ok!(Stp258Currencies::set_fee_currency(Some(ALICE).into(), Some(DNAR)));
+			assert_eq!(FeeCurrency::<Runtime>::get(&ALICE), None);
+			assert_ok!(Stp258Currencies::set_fee_currency(Some(ALICE).into(), Some(SETT)));
+			assert_ok!(Stp258Currencies::set_fee_currency(Some(ALICE).into(), None));
+			assert_eq!(FeeCurrency::<Runtime>::get(&ALICE), None);
+
+			assert_noop!(
+				Stp258Currencies::set_fee_currency(Some(ALICE).into(), Some(4)),
+				Error::<Runtime>::CurrencyNotRegistered
+			);
+			assert_noop!(Stp258Currencies::set_fee_currency(Origin::root(), Some(SETT)), BadOrigin);
+		});
+}
+
+#[test]
+fn fees_are_charged_in_native_currency_by_default() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.sett_n_jusd_registered()
+		.build()
+		.execute_with(|| {
+			let paid = FeeCharger::withdraw_fee(&ALICE, &remark_call(), &Default::default(), 10, 0);
+			assert_eq!(paid, Ok(Some((DNAR, 10))));
+			assert_eq!(Stp258Currencies::free_balance(DNAR, &ALICE), 90);
+
+			assert_ok!(FeeCharger::correct_and_deposit_fee(
+				&ALICE,
+				&Default::default(),
+				&Default::default(),
+				4,
+				0,
+				paid.unwrap()
+			));
+			assert_eq!(Stp258Currencies::free_balance(DNAR, &ALICE), 96);
+			assert_eq!(Stp258Currencies::total_issuance(DNAR), 396);
+		});
+}
+
+#[test]
+fn fees_are_charged_and_refunded_in_fee_currency() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.sett_n_jusd_registered()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Currencies::set_fee_currency(Some(ALICE).into(), Some(SETT)));
+
+			let paid = FeeCharger::withdraw_fee(&ALICE, &remark_call(), &Default::default(), 10, 0);
+			assert_eq!(paid, Ok(Some((SETT, 1_000))));
+			assert_eq!(Stp258Currencies::free_balance(SETT, &ALICE), 100 * 10_000 - 1_000);
+			assert_eq!(Stp258Currencies::free_balance(DNAR, &ALICE), 100);
+
+			assert_ok!(FeeCharger::correct_and_deposit_fee(
+				&ALICE,
+				&Default::default(),
+				&Default::default(),
+				4,
+				0,
+				paid.unwrap()
+			));
+			assert_eq!(Stp258Currencies::free_balance(SETT, &ALICE), 100 * 10_000 - 400);
+		});
+}
+
+#[test]
+fn fees_fall_back_to_native_currency() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DNAR, 100), (BOB, DNAR, 5)])
+		.sett_n_jusd_registered()
+		.build()
+		.execute_with(|| {
+			// no price for JUSD
+			assert_ok!(Stp258Currencies::set_fee_currency(Some(ALICE).into(), Some(JUSD)));
+			assert_eq!(
+				FeeCharger::withdraw_fee(&ALICE, &remark_call(), &Default::default(), 10, 0),
+				Ok(Some((DNAR, 10)))
+			);
+
+			// no SETT balance
+			assert_ok!(Stp258Currencies::set_fee_currency(Some(ALICE).into(), Some(SETT)));
+			assert_eq!(
+				FeeCharger::withdraw_fee(&ALICE, &remark_call(), &Default::default(), 10, 0),
+				Ok(Some((DNAR, 10)))
+			);
+
+			assert_ok!(Stp258Currencies::set_fee_currency(Some(BOB).into(), Some(SETT)));
+			assert_eq!(
+				FeeCharger::withdraw_fee(&BOB, &remark_call(), &Default::default(), 10, 0),
+				Err(TransactionValidityError::from(InvalidTransaction::Payment))
+			);
+		});
+}
+
+#[test]
+fn fees_keep_the_signer_alive() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.sett_n_jusd_registered()
+		.build()
+		.execute_with(|| {
+			assert_eq!(
+				FeeCharger::withdraw_fee(&ALICE, &remark_call(), &Default::default(), 100, 0),
+				Err(TransactionValidityError::from(InvalidTransaction::Payment))
+			);
+			assert_eq!(
+				FeeCharger::withdraw_fee(&ALICE, &remark_call(), &Default::default(), 99, 0),
+				Ok(Some((DNAR, 99)))
+			);
+			assert_eq!(Stp258Currencies::free_balance(DNAR, &ALICE), 1);
+		});
+}
+
+#[test]
+fn fees_fall_back_to_native_for_paused_currencies_and_frozen_accounts() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.sett_n_jusd_registered()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Currencies::set_fee_currency(Some(ALICE).into(), Some(SETT)));
+			assert_ok!(Stp258Currencies::set_fee_currency(Some(BOB).into(), Some(SETT)));
+			assert_ok!(Stp258Currencies::pause_currency(Origin::root(), SETT));
+			assert_eq!(
+				FeeCharger::withdraw_fee(&ALICE, &remark_call(), &Default::default(), 10, 0),
+				Ok(Some((DNAR, 10)))
+			);
+			assert_eq!(Stp258Currencies::free_balance(SETT, &ALICE), 100 * 10_000);
+			assert_eq!(Stp258Currencies::free_balance(DNAR, &ALICE), 90);
+
+			assert_ok!(Stp258Currencies::unpause_currency(Origin::root(), SETT));
+			assert_ok!(Stp258Currencies::freeze_account(Origin::root(), ALICE, SETT));
+			assert_eq!(
+				FeeCharger::withdraw_fee(&ALICE, &remark_call(), &Default::default(), 10, 0),
+				Ok(Some((DNAR, 10)))
+			);
+			assert_eq!(Stp258Currencies::free_balance(SETT, &ALICE), 100 * 10_000);
+			assert_eq!(Stp258Currencies::free_balance(DNAR, &ALICE), 80);
+
+			assert_eq!(
+				FeeCharger::withdraw_fee(&BOB, &remark_call(), &Default::default(), 10, 0),
+				Ok(Some((SETT, 1_000)))
+			);
+
+			assert_ok!(Stp258Currencies::freeze_account(Origin::root(), ALICE, DNAR));
+			assert_eq!(
+				FeeCharger::withdraw_fee(&ALICE, &remark_call(), &Default::default(), 10, 0),
+				Err(TransactionValidityError::from(InvalidTransaction::Payment))
+			);
+		});
+}
+
+fn sett_transfer_fee() -> TransferFee<Balance> {
+	TransferFee {
+		flat: 0,
+		rate: Perbill::from_percent(1),
+		min: 100,
+		max: 5_000,
+	}
+}
+
+#[test]
+fn set_transfer_fee_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			Stp258Currencies::set_transfer_fee(Some(ALICE).into(), SETT, Some(sett_transfer_fee())),
+			BadOrigin
+		);
+		assert_noop!(
+			Stp258Currencies::set_transfer_fee(
+				Origin::root(),
+				SETT,
+				Some(TransferFee {
+					min: 5_001,
+					..sett_transfer_fee()
+				})
+			),
+			Error::<Runtime>::InvalidTransferFee
+		);
+
+		assert_ok!(Stp258Currencies::set_transfer_fee(
+			Some(SERPER).into(),
+			SETT,
+			Some(sett_transfer_fee())
+		));
+		assert_eq!(TransferFees::<Runtime>::get(SETT), Some(sett_transfer_fee()));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == Event::stp258_currencies(crate::Event::TransferFeeSet(SETT))));
+
+		assert_eq!(Stp258Currencies::transfer_fee(SETT, 40 * 10_000), 4_000);
+		assert_eq!(Stp258Currencies::transfer_fee(SETT, 100), 100);
+		assert_eq!(Stp258Currencies::transfer_fee(SETT, 10_000 * 10_000), 5_000);
+		assert_eq!(Stp258Currencies::transfer_fee(JUSD, 10_000), 0);
+
+		assert_ok!(Stp258Currencies::set_transfer_fee(Origin::root(), SETT, None));
+		assert_eq!(TransferFees::<Runtime>::get(SETT), None);
+	});
+}
+
+#[test]
+fn transfer_fee_is_split_between_serper_and_settpay() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Stp258Currencies::set_transfer_fee(
+				Origin::root(),
+				SETT,
+				Some(sett_transfer_fee())
+			));
+
+			let post_info = Stp258Currencies::transfer(Some(ALICE).into(), BOB, SETT, 40 * 10_000).unwrap();
+			assert_eq!(
+				post_info.actual_weight,
+				Some(
+					<() as WeightInfo>::transfer_non_native_currency()
+						.saturating_add(<() as WeightInfo>::charge_transfer_fee())
+				)
+			);
+			assert_eq!(Stp258Currencies::free_balance(SETT, &ALICE), 60 * 10_000 - 4_000);
+			assert_eq!(Stp258Currencies::free_balance(SETT, &BOB), 140 * 10_000);
+			assert_eq!(Stp258Currencies::free_balance(SETT, &SERPER), 100 * 10_000 + 1_000);
+			assert_eq!(Stp258Currencies::free_balance(SETT, &SETTPAY), 100 * 10_000 + 3_000);
+			assert_eq!(Stp258Currencies::total_issuance(SETT), 400 * 10_000);
+			assert!(System::events().iter().any(|record| record.event
+				== Event::stp258_currencies(crate::Event::TransferFeeCharged(SETT, ALICE, 4_000))));
+
+			// native transfers are not charged
+			assert_ok!(Stp258Currencies::transfer(Some(ALICE).into(), BOB, DNAR, 10));
+			assert_eq!(Stp258Currencies::free_balance(DNAR, &ALICE), 90);
+		});
+}
+
+#[test]
+fn transfer_fee_can_be_paid_to_new_accounts() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DNAR, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Currencies::set_transfer_fee(
+				Origin::root(),
+				DNAR,
+				Some(TransferFee {
+					flat: 4,
+					rate: Perbill::from_percent(0),
+					min: 0,
+					max: 4,
+				})
+			));
+			assert_eq!(Stp258Currencies::total_balance(DNAR, &SERPER), 0);
+			assert_eq!(Stp258Currencies::total_balance(DNAR, &SETTPAY), 0);
+
+			assert_ok!(Stp258Currencies::transfer(Some(ALICE).into(), BOB, DNAR, 10));
+			assert_eq!(Stp258Currencies::free_balance(DNAR, &ALICE), 86);
+			assert_eq!(Stp258Currencies::free_balance(DNAR, &BOB), 10);
+			assert_eq!(Stp258Currencies::free_balance(DNAR, &SERPER), 1);
+			assert_eq!(Stp258Currencies::free_balance(DNAR, &SETTPAY), 3);
+			assert_eq!(Stp258Currencies::total_issuance(DNAR), 100);
+		});
+}
+
+#[test]
+fn transfer_is_reverted_if_fee_cannot_be_paid() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Currencies::set_transfer_fee(
+				Origin::root(),
+				SETT,
+				Some(sett_transfer_fee())
+			));
+
+			assert_noop!(
+				Stp258Currencies::transfer(Some(ALICE).into(), BOB, SETT, 100 * 10_000),
+				stp258_serp::Error::<Runtime>::BalanceTooLow
+			);
+		});
+}
+
+#[test]
+fn transfer_all_leaves_transfer_fee() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Stp258Currencies::set_transfer_fee(
+				Origin::root(),
+				SETT,
+				Some(sett_transfer_fee())
+			));
+
+			assert_ok!(Stp258Currencies::transfer_all(Some(ALICE).into(), BOB, SETT, false));
+			assert_eq!(Stp258Currencies::free_balance(SETT, &ALICE), 0);
+			assert_eq!(Stp258Currencies::free_balance(SETT, &BOB), 200 * 10_000 - 5_000);
+			assert_eq!(Stp258Currencies::free_balance(SETT, &SERPER), 100 * 10_000 + 1_250);
+			assert_eq!(Stp258Currencies::free_balance(SETT, &SETTPAY), 100 * 10_000 + 3_750);
+		});
+}
+
+#[test]
+fn amount_less_transfer_fee_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(Stp258Currencies::amount_less_transfer_fee(SETT, 1_000), 1_000);
+
+		assert_ok!(Stp258Currencies::set_transfer_fee(Origin::root(), SETT, Some(sett_transfer_fee())));
+		assert_eq!(Stp258Currencies::amount_less_transfer_fee(SETT, 50), 0);
+		assert_eq!(Stp258Currencies::amount_less_transfer_fee(SETT, 10_000), 9_900);
+		assert_eq!(Stp258Currencies::amount_less_transfer_fee(SETT, 10_100), 10_000);
+		assert_eq!(Stp258Currencies::amount_less_transfer_fee(SETT, 101_000), 100_000);
+		assert_eq!(Stp258Currencies::amount_less_transfer_fee(SETT, 1_000_000), 995_000);
+
+		assert_ok!(Stp258Currencies::set_transfer_fee(
+			Origin::root(),
+			SETT,
+			Some(TransferFee {
+				flat: 1_000,
+				..sett_transfer_fee()
+			})
+		));
+		assert_eq!(Stp258Currencies::amount_less_transfer_fee(SETT, 500), 0);
+		assert_eq!(Stp258Currencies::amount_less_transfer_fee(SETT, 102_000), 100_000);
+	});
+}
+
+fn sett_vesting_schedule() -> VestingSchedule<u64, Balance> {
+	VestingSchedule {
+		start: 0,
+		period: 10,
+		period_count: 2,
+		per_period: 10 * 10_000,
+	}
+}
+
+#[test]
+fn vesting_schedule_locked_amount_should_work() {
+	let schedule = VestingSchedule {
+		start: 10u64,
+		period: 10u64,
+		period_count: 3u32,
+		per_period: 100u64,
+	};
+	assert_eq!(schedule.total_amount(), Some(300));
+	assert_eq!(schedule.locked_amount(0), 300);
+	assert_eq!(schedule.locked_amount(19), 300);
+	assert_eq!(schedule.locked_amount(20), 200);
+	assert_eq!(schedule.locked_amount(39), 100);
+	assert_eq!(schedule.locked_amount(40), 0);
+}
+
+#[test]
+fn vested_transfer_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Stp258Currencies::vested_transfer(
+				Some(ALICE).into(),
+				BOB,
+				SETT,
+				sett_vesting_schedule()
+			));
+			assert_eq!(Stp258Currencies::free_balance(SETT, &ALICE), 80 * 10_000);
+			assert_eq!(Stp258Currencies::free_balance(SETT, &BOB), 120 * 10_000);
+			assert_eq!(VestingSchedules::<Runtime>::get(SETT, &BOB), vec![sett_vesting_schedule()]);
+			assert_eq!(Stp258Serp::locks(&BOB, SETT).len(), 1);
+			assert!(System::events().iter().any(|record| record.event
+				== Event::stp258_currencies(crate::Event::VestingScheduleAdded(
+					SETT,
+					ALICE,
+					BOB,
+					sett_vesting_schedule()
+				))));
+
+			assert_noop!(
+				Stp258Currencies::transfer(Some(BOB).into(), ALICE, SETT, 100 * 10_000 + 1),
+				stp258_serp::Error::<Runtime>::LiquidityRestrictions
+			);
+			assert_ok!(Stp258Currencies::transfer(Some(BOB).into(), ALICE, SETT, 100 * 10_000));
+		});
+}
+
+#[test]
+fn vested_transfer_fails_for_invalid_schedules() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Stp258Currencies::vested_transfer(
+					Some(ALICE).into(),
+					BOB,
+					SETT,
+					VestingSchedule {
+						period: 0,
+						..sett_vesting_schedule()
+					}
+				),
+				Error::<Runtime>::ZeroVestingPeriod
+			);
+			assert_noop!(
+				Stp258Currencies::vested_transfer(
+					Some(ALICE).into(),
+					BOB,
+					SETT,
+					VestingSchedule {
+						period_count: 0,
+						..sett_vesting_schedule()
+					}
+				),
+				Error::<Runtime>::ZeroVestingPeriodCount
+			);
+			assert_noop!(
+				Stp258Currencies::vested_transfer(
+					Some(ALICE).into(),
+					BOB,
+					SETT,
+					VestingSchedule {
+						per_period: Balance::max_value(),
+						..sett_vesting_schedule()
+					}
+				),
+				Error::<Runtime>::VestingOverflow
+			);
+
+			for _ in 0..3 {
+				assert_ok!(Stp258Currencies::vested_transfer(
+					Some(ALICE).into(),
+					BOB,
+					SETT,
+					sett_vesting_schedule()
+				));
+			}
+			assert_noop!(
+				Stp258Currencies::vested_transfer(Some(ALICE).into(), BOB, SETT, sett_vesting_schedule()),
+				Error::<Runtime>::TooManyVestingSchedules
+			);
+		});
+}
+
+#[test]
+fn claim_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Currencies::vested_transfer(
+				Some(ALICE).into(),
+				BOB,
+				DNAR,
+				VestingSchedule {
+					start: 0,
+					period: 10,
+					period_count: 2,
+					per_period: 10,
+				}
+			));
+			assert_eq!(PalletBalances::locks(&BOB)[0].amount, 20);
+
+			System::set_block_number(10);
+			assert_ok!(Stp258Currencies::claim(Some(BOB).into(), DNAR));
+			assert_eq!(PalletBalances::locks(&BOB)[0].amount, 10);
+			assert!(System::events()
+				.iter()
+				.any(|record| record.event == Event::stp258_currencies(crate::Event::Claimed(DNAR, BOB, 10))));
+
+			System::set_block_number(20);
+			assert_ok!(Stp258Currencies::claim(Some(BOB).into(), DNAR));
+			assert!(PalletBalances::locks(&BOB).is_empty());
+			assert!(!VestingSchedules::<Runtime>::contains_key(DNAR, &BOB));
+
+			let lock_removed_event =
+				Event::stp258_currencies(crate::Event::LockRemoved(VESTING_LOCK_ID, DNAR, BOB));
+			let lock_removals = || {
+				System::events()
+					.into_iter()
+					.filter(|record| record.event == lock_removed_event)
+					.count()
+			};
+			assert_eq!(lock_removals(), 1);
+			assert_ok!(Stp258Currencies::claim(Some(BOB).into(), DNAR));
+			assert_eq!(lock_removals(), 1);
+		});
+}
+
+#[test]
+fn update_vesting_schedules_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Stp258Currencies::update_vesting_schedules(
+					Some(ALICE).into(),
+					BOB,
+					SETT,
+					vec![sett_vesting_schedule()]
+				),
+				BadOrigin
+			);
+			assert_noop!(
+				Stp258Currencies::update_vesting_schedules(
+					Origin::root(),
+					BOB,
+					SETT,
+					vec![
+						VestingSchedule {
+							per_period: 60 * 10_000,
+							..sett_vesting_schedule()
+						};
+						1
+					]
+				),
+				Error::<Runtime>::InsufficientBalanceToLock
+			);
+
+			assert_ok!(Stp258Currencies::update_vesting_schedules(
+				Some(SERPER).into(),
+				BOB,
+				SETT,
+				vec![sett_vesting_schedule(); 2]
+			));
+			assert_eq!(VestingSchedules::<Runtime>::get(SETT, &BOB).len(), 2);
+			assert_eq!(Stp258Serp::locks(&BOB, SETT)[0].amount, 40 * 10_000);
+
+			assert_ok!(Stp258Currencies::update_vesting_schedules(Origin::root(), BOB, SETT, vec![]));
+			assert!(Stp258Serp::locks(&BOB, SETT).is_empty());
+		});
+}
+
+#[test]
+fn genesis_vesting_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.vesting(vec![(ALICE, DNAR, 0, 10, 5, 10), (BOB, SETT, 0, 10, 2, 10 * 10_000)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(PalletBalances::locks(&ALICE)[0].amount, 50);
+			assert_eq!(Stp258Serp::locks(&BOB, SETT)[0].amount, 20 * 10_000);
+			assert_eq!(VestingSchedules::<Runtime>::get(SETT, &BOB), vec![sett_vesting_schedule()]);
+		});
+}
+
+#[test]
+fn schedule_transfer_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Stp258Currencies::schedule_transfer(
+				Some(ALICE).into(),
+				BOB,
+				JUSD,
+				10 * 1_000,
+				5
+			));
+			assert_eq!(Stp258Currencies::free_balance(JUSD, &ALICE), 90 * 1_000);
+			assert_eq!(Stp258Currencies::reserved_balance(JUSD, &ALICE), 10 * 1_000);
+			assert_eq!(
+				Stp258Currencies::reserved_balance_named(&SCHEDULED_TRANSFER_RESERVE_ID, JUSD, &ALICE),
+				10 * 1_000
+			);
+			assert_eq!(TransferAgenda::<Runtime>::get(5), vec![0]);
+			assert_eq!(
+				Stp258Currencies::scheduled_transfer(0),
+				Some(ScheduledTransfer {
+					from: ALICE,
+					to: BOB,
+					currency_id: JUSD,
+					amount: 10 * 1_000,
+					at: 5,
+				})
+			);
+			assert!(System::events().iter().any(|record| record.event
+				== Event::stp258_currencies(crate::Event::TransferScheduled(0, JUSD, ALICE, BOB, 10 * 1_000, 5))));
+
+			Stp258Currencies::on_initialize(4);
+			assert_eq!(Stp258Currencies::free_balance(JUSD, &BOB), 100 * 1_000);
+
+			System::set_block_number(5);
+			Stp258Currencies::on_initialize(5);
+			assert_eq!(Stp258Currencies::reserved_balance(JUSD, &ALICE), 0);
+			assert_eq!(Stp258Currencies::free_balance(JUSD, &ALICE), 90 * 1_000);
+			assert_eq!(Stp258Currencies::free_balance(JUSD, &BOB), 110 * 1_000);
+			assert_eq!(Stp258Currencies::scheduled_transfer(0), None);
+			assert!(!TransferAgenda::<Runtime>::contains_key(5));
+			assert!(System::events()
+				.iter()
+				.any(|record| record.event == Event::stp258_currencies(crate::Event::ScheduledTransferExecuted(0))));
+		});
+}
+
+#[test]
+fn scheduled_transfer_sends_only_what_is_still_reserved() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Currencies::schedule_transfer(Some(ALICE).into(), BOB, JUSD, 10 * 1_000, 5));
+			assert_eq!(
+				Stp258Currencies::slash_reserved_named(&SCHEDULED_TRANSFER_RESERVE_ID, JUSD, &ALICE, 4 * 1_000),
+				0
+			);
+
+			System::set_block_number(5);
+			Stp258Currencies::on_initialize(5);
+			assert_eq!(Stp258Currencies::reserved_balance(JUSD, &ALICE), 0);
+			assert_eq!(Stp258Currencies::free_balance(JUSD, &ALICE), 90 * 1_000);
+			assert_eq!(Stp258Currencies::free_balance(JUSD, &BOB), 106 * 1_000);
+			assert!(System::events()
+				.iter()
+				.any(|record| record.event == Event::stp258_currencies(crate::Event::ScheduledTransferExecuted(0))));
+		});
+}
+
+#[test]
+fn schedule_transfer_fails_if_past_or_agenda_full() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(5);
+			assert_noop!(
+				Stp258Currencies::schedule_transfer(Some(ALICE).into(), BOB, JUSD, 1_000, 5),
+				Error::<Runtime>::ScheduledInPast
+			);
+
+			assert_ok!(Stp258Currencies::schedule_transfer(Some(ALICE).into(), BOB, JUSD, 1_000, 6));
+			assert_ok!(Stp258Currencies::schedule_transfer(Some(BOB).into(), ALICE, SETT, 10_000, 6));
+			assert_noop!(
+				Stp258Currencies::schedule_transfer(Some(ALICE).into(), BOB, JUSD, 1_000, 6),
+				Error::<Runtime>::TooManyScheduledTransfers
+			);
+			assert_ok!(Stp258Currencies::schedule_transfer(Some(ALICE).into(), BOB, JUSD, 1_000, 7));
+			assert_eq!(NextScheduledTransferId::<Runtime>::get(), 3);
+		});
+}
+
+#[test]
+fn cancel_scheduled_transfer_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Currencies::reserve(JUSD, &ALICE, 5 * 1_000));
+			assert_ok!(Stp258Currencies::schedule_transfer(Some(ALICE).into(), BOB, JUSD, 10 * 1_000, 5));
+			assert_ok!(Stp258Currencies::schedule_transfer(Some(ALICE).into(), BOB, DNAR, 10, 5));
+
+			assert_noop!(
+				Stp258Currencies::cancel_scheduled_transfer(Some(ALICE).into(), 2),
+				Error::<Runtime>::ScheduledTransferNotFound
+			);
+			assert_noop!(
+				Stp258Currencies::cancel_scheduled_transfer(Some(BOB).into(), 0),
+				Error::<Runtime>::NotScheduledTransferSender
+			);
+
+			assert_ok!(Stp258Currencies::cancel_scheduled_transfer(Some(ALICE).into(), 0));
+			assert_eq!(Stp258Currencies::reserved_balance(JUSD, &ALICE), 5 * 1_000);
+			assert_eq!(
+				Stp258Currencies::reserved_balance_named(&SCHEDULED_TRANSFER_RESERVE_ID, JUSD, &ALICE),
+				0
+			);
+			assert_eq!(Stp258Currencies::free_balance(JUSD, &ALICE), 95 * 1_000);
+			assert_eq!(TransferAgenda::<Runtime>::get(5), vec![1]);
+			assert!(System::events()
+				.iter()
+				.any(|record| record.event == Event::stp258_currencies(crate::Event::ScheduledTransferCancelled(0))));
+
+			assert_ok!(Stp258Currencies::cancel_scheduled_transfer(Some(ALICE).into(), 1));
+			assert_eq!(Stp258Currencies::reserved_balance(DNAR, &ALICE), 0);
+			assert!(!TransferAgenda::<Runtime>::contains_key(5));
+
+			Stp258Currencies::on_initialize(5);
+			assert_eq!(Stp258Currencies::free_balance(JUSD, &BOB), 100 * 1_000);
+		});
+}
+
+#[test]
+fn failed_scheduled_transfer_unreserves() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Currencies::schedule_transfer(Some(ALICE).into(), BOB, JUSD, 10 * 1_000, 5));
+			assert_ok!(Stp258Currencies::pause_currency(Origin::root(), JUSD));
+
+			Stp258Currencies::on_initialize(5);
+			assert_eq!(Stp258Currencies::reserved_balance(JUSD, &ALICE), 0);
+			assert_eq!(Stp258Currencies::free_balance(JUSD, &ALICE), 100 * 1_000);
+			assert_eq!(Stp258Currencies::free_balance(JUSD, &BOB), 100 * 1_000);
+			assert_eq!(Stp258Currencies::scheduled_transfer(0), None);
+			assert!(System::events().iter().any(|record| record.event
+				== Event::stp258_currencies(crate::Event::ScheduledTransferFailed(
+					0,
+					Error::<Runtime>::CurrencyPaused.into()
+				))));
+		});
+}
+
+#[test]
+fn create_subscription_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_noop!(
+				Stp258Currencies::create_subscription(Some(ALICE).into(), BOB, SETT, 10_000, 0, None),
+				Error::<Runtime>::InvalidSubscription
+			);
+			assert_noop!(
+				Stp258Currencies::create_subscription(Some(ALICE).into(), BOB, SETT, 10_000, 10, Some(0)),
+				Error::<Runtime>::InvalidSubscription
+			);
+			assert_noop!(
+				Stp258Currencies::create_subscription(Some(ALICE).into(), BOB, SETT, 0, 10, None),
+				Error::<Runtime>::ZeroSubscriptionAmount
+			);
+			assert_noop!(
+				Stp258Currencies::create_subscription(Some(ALICE).into(), ALICE, SETT, 10_000, 10, None),
+				Error::<Runtime>::SelfSubscription
+			);
+
+			assert_ok!(Stp258Currencies::create_subscription(
+				Some(ALICE).into(),
+				BOB,
+				SETT,
+				10_000,
+				10,
+				Some(2)
+			));
+			assert_eq!(
+				Stp258Currencies::subscription(0),
+				Some(Subscription {
+					payer: ALICE,
+					merchant: BOB,
+					currency_id: SETT,
+					amount: 10_000,
+					period: 10,
+					next_due: 1,
+					payments_made: 0,
+					max_payments: Some(2),
+					deposit: 5,
+				})
+			);
+			assert_eq!(Stp258Currencies::reserved_balance_named(&SUBSCRIPTION_RESERVE_ID, DNAR, &ALICE), 5);
+			assert_eq!(Stp258Currencies::free_balance(DNAR, &ALICE), 95);
+			assert!(System::events().iter().any(|record| record.event
+				== Event::stp258_currencies(crate::Event::SubscriptionCreated(0, SETT, ALICE, BOB, 10_000, 10))));
+		});
+}
+
+#[test]
+fn collect_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Currencies::create_subscription(
+				Some(ALICE).into(),
+				BOB,
+				SETT,
+				10_000,
+				10,
+				Some(2)
+			));
+
+			assert_noop!(
+				Stp258Currencies::collect(Some(ALICE).into(), 0),
+				Error::<Runtime>::NotSubscriptionMerchant
+			);
+			assert_ok!(Stp258Currencies::collect(Some(BOB).into(), 0));
+			assert_eq!(Stp258Currencies::free_balance(SETT, &ALICE), 99 * 10_000);
+			assert_eq!(Stp258Currencies::free_balance(SETT, &BOB), 101 * 10_000);
+			assert_eq!(Stp258Currencies::subscription(0).map(|s| (s.next_due, s.payments_made)), Some((11, 1)));
+			assert!(System::events()
+				.iter()
+				.any(|record| record.event == Event::stp258_currencies(crate::Event::SubscriptionCollected(0, 1))));
+
+			assert_noop!(
+				Stp258Currencies::collect(Some(BOB).into(), 0),
+				Error::<Runtime>::SubscriptionNotDue
+			);
+
+			System::set_block_number(11);
+			assert_ok!(Stp258Currencies::collect(Some(BOB).into(), 0));
+			assert_eq!(Stp258Currencies::free_balance(SETT, &BOB), 102 * 10_000);
+			assert_eq!(Stp258Currencies::subscription(0), None);
+			assert_eq!(Stp258Currencies::reserved_balance(DNAR, &ALICE), 0);
+			assert!(System::events()
+				.iter()
+				.any(|record| record.event == Event::stp258_currencies(crate::Event::SubscriptionCompleted(0))));
+			assert_noop!(
+				Stp258Currencies::collect(Some(BOB).into(), 0),
+				Error::<Runtime>::SubscriptionNotFound
+			);
+		});
+}
+
+#[test]
+fn collect_fails_if_balance_too_low() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Currencies::create_subscription(
+				Some(ALICE).into(),
+				BOB,
+				SETT,
+				60 * 10_000,
+				10,
+				None
+			));
+			assert_ok!(Stp258Currencies::collect(Some(BOB).into(), 0));
+
+			System::set_block_number(11);
+			assert_noop!(
+				Stp258Currencies::collect(Some(BOB).into(), 0),
+				stp258_serp::Error::<Runtime>::BalanceTooLow
+			);
+
+			assert_ok!(Stp258Currencies::set_transfer_fee(Origin::root(), SETT, Some(sett_transfer_fee())));
+			assert_ok!(Stp258Currencies::transfer(Some(SERPER).into(), ALICE, SETT, 20 * 10_000));
+			assert_noop!(
+				Stp258Currencies::collect(Some(BOB).into(), 0),
+				stp258_serp::Error::<Runtime>::BalanceTooLow
+			);
+
+			assert_ok!(Stp258Currencies::transfer(Some(SETTPAY).into(), ALICE, SETT, 10 * 10_000));
+			assert_ok!(Stp258Currencies::collect(Some(BOB).into(), 0));
+			assert_eq!(Stp258Currencies::free_balance(SETT, &BOB), 220 * 10_000);
+		});
+}
+
+#[test]
+fn collect_skips_missed_periods() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Currencies::create_subscription(Some(ALICE).into(), BOB, SETT, 10_000, 10, None));
+
+			System::set_block_number(35);
+			assert_ok!(Stp258Currencies::collect(Some(BOB).into(), 0));
+			assert_eq!(Stp258Currencies::free_balance(SETT, &BOB), 101 * 10_000);
+			assert_eq!(Stp258Currencies::subscription(0).map(|s| (s.next_due, s.payments_made)), Some((41, 1)));
+			assert_noop!(
+				Stp258Currencies::collect(Some(BOB).into(), 0),
+				Error::<Runtime>::SubscriptionNotDue
+			);
+
+			System::set_block_number(41);
+			assert_ok!(Stp258Currencies::collect(Some(BOB).into(), 0));
+			assert_eq!(Stp258Currencies::free_balance(SETT, &BOB), 102 * 10_000);
+			assert_eq!(Stp258Currencies::subscription(0).map(|s| (s.next_due, s.payments_made)), Some((51, 2)));
+		});
+}
+
+#[test]
+fn cancel_subscription_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob_n_serper_n_settpay()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Stp258Currencies::create_subscription(Some(ALICE).into(), BOB, SETT, 10_000, 10, None));
+			assert_ok!(Stp258Currencies::create_subscription(Some(ALICE).into(), BOB, SETT, 10_000, 10, None));
+
+			assert_noop!(
+				Stp258Currencies::cancel_subscription(Some(SERPER).into(), 0),
+				Error::<Runtime>::NotSubscriptionParty
+			);
+			assert_noop!(
+				Stp258Currencies::cancel_subscription(Some(ALICE).into(), 2),
+				Error::<Runtime>::SubscriptionNotFound
+			);
+
+			assert_eq!(Stp258Currencies::reserved_balance(DNAR, &ALICE), 10);
+			assert_ok!(Stp258Currencies::cancel_subscription(Some(ALICE).into(), 0));
+			assert_ok!(Stp258Currencies::cancel_subscription(Some(BOB).into(), 1));
+			assert_eq!(Stp258Currencies::subscription(0), None);
+			assert_eq!(Stp258Currencies::subscription(1), None);
+			assert_eq!(Stp258Currencies::reserved_balance(DNAR, &ALICE), 0);
+			assert!(System::events()
+				.iter()
+				.any(|record| record.event == Event::stp258_currencies(crate::Event::SubscriptionCancelled(1, BOB))));
+			assert_noop!(
+				Stp258Currencies::collect(Some(BOB).into(), 0),
+				Error::<Runtime>::SubscriptionNotFound
+			);
+		});
+}